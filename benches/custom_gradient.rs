@@ -1,9 +1,9 @@
 use colorgrad::{
-    BasisGradient, BlendMode, CatmullRomGradient, Gradient, GradientBuilder, LinearGradient,
+    BasisGradient, BlendMode, CatmullRomGradient, Color, Gradient, GradientBuilder, LinearGradient,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-const COLORS: [&'static str; 104] = [
+const COLORS: [&str; 104] = [
     "#87e575", "#e88ef2", "#7398ef", "#65c3f2", "#3e52a0", "#b659db", "#75b7ff", "#7555ba",
     "#fceac4", "#e8009e", "#cc7c26", "#e175f4", "#f959e7", "#31828e", "#e4bef7", "#a9fcc6",
     "#c122d6", "#81f9e1", "#caea81", "#47d192", "#db579d", "#ead36b", "#3c2bbc", "#9de544",
@@ -18,11 +18,12 @@ const COLORS: [&'static str; 104] = [
     "#f9c0b3", "#9890db", "#d01be8", "#20870e", "#f4426b", "#def260", "#521efc", "#ffbcc6",
     "#e285b9", "#0ed6f9", "#7825ed", "#f2c6ff", "#cdb2f4", "#5fd374", "#fc838d", "#27bec6",
 ];
-const MODES: [BlendMode; 4] = [
+const MODES: [BlendMode; 5] = [
     BlendMode::Rgb,
     BlendMode::LinearRgb,
     BlendMode::Oklab,
     BlendMode::Lab,
+    BlendMode::Lch,
 ];
 const POSITIONS: [f32; 3] = [0.03, 0.5, 0.97];
 
@@ -44,6 +45,42 @@ fn bench_linear_gradient(c: &mut Criterion) {
     }
 }
 
+fn bench_linear_gradient_scanline(c: &mut Criterion) {
+    let grad = GradientBuilder::new()
+        .html_colors(&COLORS)
+        .build::<LinearGradient>()
+        .unwrap();
+    let width = 1000;
+
+    c.bench_function("LinearGradient row at()", |b| {
+        b.iter(|| {
+            for x in 0..width {
+                black_box(grad.at(x as f32 / width as f32));
+            }
+        })
+    });
+
+    c.bench_function("LinearGradient row scanline_sampler()", |b| {
+        b.iter(|| {
+            let mut sampler = grad.scanline_sampler();
+            for x in 0..width {
+                black_box(sampler.at(x as f32 / width as f32));
+            }
+        })
+    });
+
+    let ts = (0..width)
+        .map(|x| x as f32 / width as f32)
+        .collect::<Vec<_>>();
+    let mut out = vec![Color::default(); width];
+
+    c.bench_function("LinearGradient row at_sorted()", |b| {
+        b.iter(|| {
+            grad.at_sorted(black_box(&ts), &mut out);
+        })
+    });
+}
+
 fn bench_catmull_rom_gradient(c: &mut Criterion) {
     for mode in MODES {
         let grad = GradientBuilder::new()
@@ -80,7 +117,25 @@ fn bench_basis_gradient(c: &mut Criterion) {
     }
 }
 
-criterion_group!(linear_gradient, bench_linear_gradient,);
+fn bench_build_linear_gradient(c: &mut Criterion) {
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#ff0", "#0ff"]);
+
+    c.bench_function("LinearGradient build 1000x 2 stops", |b| {
+        b.iter(|| {
+            for _ in 0..1000 {
+                black_box(gb.build::<LinearGradient>().unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(
+    linear_gradient,
+    bench_linear_gradient,
+    bench_build_linear_gradient,
+    bench_linear_gradient_scanline,
+);
 criterion_group!(catmull_rom_gradient, bench_catmull_rom_gradient,);
 criterion_group!(basis_gradient, bench_basis_gradient,);
 criterion_main!(linear_gradient, catmull_rom_gradient, basis_gradient);
@@ -2,8 +2,8 @@ use colorgrad::{Color, GimpGradient, Gradient};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::io::BufReader;
 
-const GGR1: &'static str = include_str!("../examples/ggr/My_Gradient.ggr");
-const GGR2: &'static str = include_str!("../examples/ggr/test_hsv.ggr");
+const GGR1: &str = include_str!("../examples/ggr/My_Gradient.ggr");
+const GGR2: &str = include_str!("../examples/ggr/test_hsv.ggr");
 
 const POSITIONS: [f32; 4] = [0.03, 0.45, 0.55, 0.97];
 
@@ -28,6 +28,15 @@ fn bench_turbo(c: &mut Criterion) {
     });
 }
 
+fn bench_turbo_lut(c: &mut Criterion) {
+    let grad = preset::turbo_lut(256);
+    c.bench_function("preset turbo_lut", |b| {
+        b.iter(|| {
+            grad.at(black_box(0.6));
+        })
+    });
+}
+
 fn bench_cividis(c: &mut Criterion) {
     let grad = preset::cividis();
     c.bench_function("preset cividis", |b| {
@@ -78,6 +87,7 @@ criterion_group!(
     bench_sinebow,
     bench_rainbow,
     bench_turbo,
+    bench_turbo_lut,
     bench_cividis,
     bench_cubehelix,
     bench_warm,
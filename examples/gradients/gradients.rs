@@ -64,6 +64,7 @@ pub fn blend_mode() -> Vec<(Box<dyn Gradient>, &'static str)> {
         (Box::new(grad(BlendMode::LinearRgb)), "LinearRgb"),
         (Box::new(grad(BlendMode::Oklab)), "Oklab"),
         (Box::new(grad(BlendMode::Lab)), "Lab"),
+        (Box::new(grad(BlendMode::Lch)), "Lch"),
     ]
 }
 
@@ -75,6 +76,7 @@ pub fn interpolation() -> Vec<(Box<dyn Gradient>, String)> {
         BlendMode::LinearRgb,
         BlendMode::Oklab,
         BlendMode::Lab,
+        BlendMode::Lch,
     ];
 
     for mode in modes.iter() {
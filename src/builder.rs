@@ -1,7 +1,11 @@
 use std::convert::TryFrom;
 use std::{error, fmt};
 
-use crate::{css_gradient, linspace, BlendMode, Color};
+use crate::gradient::linear::linear_interpolation;
+use crate::{
+    convert_colors, css_gradient, is_monotone, linspace, to_color, BlendMode, ChannelMode, Color,
+    Gradient, HueDirection,
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum GradientBuilderError {
@@ -9,6 +13,8 @@ pub enum GradientBuilderError {
     InvalidCssGradient,
     InvalidDomain,
     InvalidStops,
+    /// [`GradientBuilder::channel_modes`] is set, but not supported by the gradient type
+    UnsupportedChannelModes,
 }
 
 impl fmt::Display for GradientBuilderError {
@@ -28,12 +34,29 @@ impl fmt::Display for GradientBuilderError {
             Self::InvalidCssGradient => f.write_str("invalid css gradient"),
             Self::InvalidDomain => f.write_str("invalid domain"),
             Self::InvalidStops => f.write_str("invalid stops"),
+            Self::UnsupportedChannelModes => f.write_str("unsupported channel modes"),
         }
     }
 }
 
 impl error::Error for GradientBuilderError {}
 
+/// Informational warning recorded while building a gradient, see
+/// [`GradientBuilder::warnings`]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum GradientBuilderWarning {
+    /// The perceived lightness is not monotonic, which is misleading for sequential data
+    NonMonotoneLightness,
+}
+
+impl fmt::Display for GradientBuilderWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::NonMonotoneLightness => f.write_str("non-monotone lightness"),
+        }
+    }
+}
+
 /// Create custom gradient
 ///
 /// # Examples
@@ -84,8 +107,18 @@ pub struct GradientBuilder {
     pub(crate) colors: Vec<Color>,
     pub(crate) positions: Vec<f32>,
     pub(crate) mode: BlendMode,
+    // CSS interpolation hint of each segment, relative to the segment
+    pub(crate) hints: Vec<Option<f32>>,
+    position_curve: f32,
+    pub(crate) channel_modes: Option<[ChannelMode; 3]>,
+    pub(crate) hue_direction: HueDirection,
+    reverse: bool,
+    dedup_adjacent: bool,
+    warn_non_monotone: bool,
+    warnings: Vec<GradientBuilderWarning>,
     invalid_html_colors: Vec<String>,
     invalid_css_gradient: bool,
+    invalid_positions: bool,
     clean: bool,
 }
 
@@ -96,8 +129,17 @@ impl GradientBuilder {
             colors: Vec::new(),
             positions: Vec::new(),
             mode: BlendMode::Rgb,
+            hints: Vec::new(),
+            position_curve: 1.0,
+            channel_modes: None,
+            hue_direction: HueDirection::Shorter,
+            reverse: false,
+            dedup_adjacent: false,
+            warn_non_monotone: false,
+            warnings: Vec::new(),
             invalid_html_colors: Vec::new(),
             invalid_css_gradient: false,
+            invalid_positions: false,
             clean: false,
         }
     }
@@ -111,6 +153,57 @@ impl GradientBuilder {
         self
     }
 
+    /// Set gradient color using RGBA8 arrays
+    pub fn colors_rgba8(&mut self, colors: &[[u8; 4]]) -> &mut Self {
+        for [r, g, b, a] in colors {
+            self.colors.push(Color::from_rgba8(*r, *g, *b, *a));
+        }
+        self.clean = false;
+        self
+    }
+
+    /// Append `n` colors evenly spaced across gradient `g`, positioned evenly across
+    /// `[start, end]`.
+    ///
+    /// The positions are appended too, so every color of the builder must have a position,
+    /// e.g. by only using `sample_from()`.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let blue = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#fff", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    /// let red = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#000"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// // blue for the low half, red for the high half
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .sample_from(&blue, 5, 0.0, 0.5)
+    ///     .sample_from(&red, 5, 0.5, 1.0)
+    ///     .build::<colorgrad::LinearGradient>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample_from(&mut self, g: &dyn Gradient, n: usize, start: f32, end: f32) -> &mut Self {
+        self.colors.extend(g.colors(n));
+        self.positions.extend(linspace(start, end, n));
+        self.clean = false;
+        self
+    }
+
+    /// Replace the colors and positions with `n` colors evenly spaced across gradient `g`,
+    /// keeping its domain. Useful to rebuild a gradient using another interpolation.
+    pub fn from_gradient(&mut self, g: &dyn Gradient, n: usize) -> &mut Self {
+        let (dmin, dmax) = g.domain();
+        self.colors.clear();
+        self.positions.clear();
+        self.hints.clear();
+        self.invalid_positions = false;
+        self.sample_from(g, n, dmin, dmax)
+    }
+
     /// Set gradient color using web / CSS color format.
     ///
     /// ## Supported Color Format
@@ -140,9 +233,158 @@ impl GradientBuilder {
         self
     }
 
+    /// Like [`html_colors`](GradientBuilder::html_colors), but returns the parse result of
+    /// each color, in order.
+    ///
+    /// The error is the parse error message. Invalid colors are still recorded, building
+    /// the gradient returns [`GradientBuilderError::InvalidHtmlColors`].
+    ///
+    /// ```
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// let res = gb.try_html_colors(&["#ffd700", "#zzz", "#f0f"]);
+    ///
+    /// assert!(res[0].is_ok());
+    /// assert!(res[1].is_err());
+    /// assert!(res[2].is_ok());
+    /// ```
+    pub fn try_html_colors(&mut self, colors: &[&str]) -> Vec<Result<(), String>> {
+        self.clean = false;
+        colors
+            .iter()
+            .map(|s| match csscolorparser::parse(s) {
+                Ok(c) => {
+                    self.colors.push(c);
+                    Ok(())
+                }
+                Err(e) => {
+                    self.invalid_html_colors.push(s.to_string());
+                    Err(e.to_string())
+                }
+            })
+            .collect()
+    }
+
     /// Set the gradient domain and/or color position.
     pub fn domain<'a>(&'a mut self, positions: &[f32]) -> &'a mut Self {
         self.positions = positions.to_vec();
+        self.invalid_positions = false;
+        self.clean = false;
+        self
+    }
+
+    /// Reset the gradient domain to `[0, 1]`, clearing the custom domain and color positions.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#00f"]).domain(&[0.0, 100.0]);
+    ///
+    /// let g = gb.domain_unit().build::<colorgrad::LinearGradient>()?;
+    /// assert_eq!(g.domain(), (0.0, 1.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn domain_unit(&mut self) -> &mut Self {
+        self.positions.clear();
+        self.invalid_positions = false;
+        self.clean = false;
+        self
+    }
+
+    /// Set the power curve used to place the colors when their positions are not specified.
+    ///
+    /// The color at index `i` of `n` colors is placed at `(i / (n - 1)).powf(power)` across
+    /// the domain. The default `1.0` spaces the colors evenly, a `power` greater than `1.0`
+    /// puts more colors at the start of the domain.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#000", "#777", "#fff"])
+    ///     .position_curve(2.0)
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(gb.get_positions(), &[0.0, 0.25, 1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn position_curve(&mut self, power: f32) -> &mut Self {
+        self.position_curve = power;
+        self.clean = false;
+        self
+    }
+
+    /// Reverse the colors order, the positions are mirrored within the domain.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .domain(&[0.0, 0.2, 1.0])
+    ///     .reverse()
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(gb.get_positions(), &[0.0, 0.8, 1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reverse(&mut self) -> &mut Self {
+        self.reverse = !self.reverse;
+        self.clean = false;
+        self
+    }
+
+    /// Merge consecutive stops with identical colors into a single stop, keeping the
+    /// position of the first one, to avoid unwanted flat regions.
+    ///
+    /// The domain is preserved, identical colors at the end of the gradient keep the
+    /// position of the last one.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#f00", "#00f"])
+    ///     .dedup_adjacent()
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(gb.get_positions(), &[0.0, 1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dedup_adjacent(&mut self) -> &mut Self {
+        self.dedup_adjacent = true;
+        self.clean = false;
+        self
+    }
+
+    /// Check whether the perceived lightness (Oklab `L`) of the gradient is monotonic when
+    /// building it, and record a [`GradientBuilderWarning::NonMonotoneLightness`] if not.
+    ///
+    /// Rainbow-like gradients are bad for sequential data. The warning is informational,
+    /// building doesn't fail.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::GradientBuilderWarning;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .warn_non_monotone(true)
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(gb.warnings(), &[GradientBuilderWarning::NonMonotoneLightness]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn warn_non_monotone(&mut self, enable: bool) -> &mut Self {
+        self.warn_non_monotone = enable;
         self.clean = false;
         self
     }
@@ -153,8 +395,177 @@ impl GradientBuilder {
         self
     }
 
+    /// Interpolate the hue, saturation and lightness channels of HSL color space
+    /// independently, each using its own [`ChannelMode`]. Overrides the blend mode.
+    ///
+    /// Only supported by [`LinearGradient`](crate::LinearGradient), building other
+    /// gradients returns [`GradientBuilderError::UnsupportedChannelModes`].
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{ChannelMode, Gradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// // red to magenta through pink instead of through green
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#f0f"])
+    ///     .channel_modes([ChannelMode::Circular, ChannelMode::Linear, ChannelMode::Linear])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(grad.at(0.5).to_css_hex(), "#ff0080");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn channel_modes(&mut self, modes: [ChannelMode; 3]) -> &mut Self {
+        self.channel_modes = Some(modes);
+        self.clean = false;
+        self
+    }
+
+    /// Set the direction of hue interpolation, for hue based blending: [`BlendMode::Hsl`],
+    /// `BlendMode::Lch` and the [`ChannelMode::Circular`] channel modes. Default to
+    /// [`HueDirection::Shorter`].
+    ///
+    /// Gradients between equal hues keep a constant hue in every direction.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{ChannelMode, Gradient, HueDirection};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// // red to magenta the long way, through yellow, green and blue
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#f0f"])
+    ///     .channel_modes([ChannelMode::Circular, ChannelMode::Linear, ChannelMode::Linear])
+    ///     .hue_direction(HueDirection::Longer)
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(grad.at(0.5).to_css_hex(), "#00ff80");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hue_direction(&mut self, direction: HueDirection) -> &mut Self {
+        self.hue_direction = direction;
+        self.clean = false;
+        self
+    }
+
+    /// Set the transition hint of each segment between two colors, like the CSS gradient
+    /// hints but relative to the segment.
+    ///
+    /// A hint moves the midpoint of the transition to that relative position, `0.5` or
+    /// `None` is the plain linear transition. Hints are clamped to `[0, 1]`, the hints
+    /// of missing segments are `None` and extra hints are ignored.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .hints(&[Some(0.25)])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(grad.at(0.25).to_css_hex(), "#800080");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hints(&mut self, hints: &[Option<f32>]) -> &mut Self {
+        self.hints = hints
+            .iter()
+            .map(|h| h.filter(|h| !h.is_nan()).map(|h| h.clamp(0.0, 1.0)))
+            .collect();
+        self.clean = false;
+        self
+    }
+
+    /// Set the colors and their positions from `(value, color)` measurements, in any order.
+    ///
+    /// The measurements are sorted by value, the values become the positions and the
+    /// domain. Building fails with [`GradientBuilderError::InvalidDomain`] if a value is
+    /// repeated or not finite, until the positions are replaced (e.g. using
+    /// [`domain`](Self::domain) or [`css`](Self::css)).
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{Color, Gradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .measured(&[
+    ///         (25.0, Color::new(1.0, 0.0, 0.0, 1.0)),
+    ///         (-5.0, Color::new(0.0, 0.0, 1.0, 1.0)),
+    ///         (10.0, Color::new(1.0, 1.0, 1.0, 1.0)),
+    ///     ])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(grad.domain(), (-5.0, 25.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn measured(&mut self, data: &[(f32, Color)]) -> &mut Self {
+        let mut data = data.to_vec();
+        data.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        self.invalid_positions =
+            data.iter().any(|(v, _)| !v.is_finite()) || data.windows(2).any(|w| w[0].0 >= w[1].0);
+        self.colors = data.iter().map(|(_, c)| c.clone()).collect();
+        self.positions = data.iter().map(|(v, _)| *v).collect();
+        self.hints.clear();
+        self.clean = false;
+        self
+    }
+
+    /// Insert a color stop at `pos`, keeping the positions sorted.
+    ///
+    /// The positions must be explicit, one for each color, like set using
+    /// [`domain`](Self::domain) or [`measured`](Self::measured). Otherwise, or if `pos` is not
+    /// finite, building fails with [`GradientBuilderError::InvalidDomain`] until the positions
+    /// are replaced. A stop inserted at the position of an existing stop is placed after it,
+    /// making a hard edge.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{Color, Gradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#00f"]).domain(&[0.0, 1.0]);
+    ///
+    /// let grad = gb
+    ///     .insert_stop(0.5, Color::new(1.0, 1.0, 1.0, 1.0))
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(grad.at(0.5).to_css_hex(), "#ffffff");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_stop(&mut self, pos: f32, color: Color) -> &mut Self {
+        if !pos.is_finite() || self.positions.len() != self.colors.len() {
+            self.invalid_positions = true;
+            return self;
+        }
+
+        let i = self.positions.partition_point(|&p| p <= pos);
+        self.positions.insert(i, pos);
+        self.colors.insert(i, color);
+        // the new stop splits a segment, the hint stays with its first part
+        if i <= self.hints.len() {
+            self.hints.insert(i, None);
+        }
+        self.clean = false;
+        self
+    }
+
     /// Parse [CSS gradient](https://developer.mozilla.org/en-US/docs/Web/CSS/gradient/linear-gradient) format
     ///
+    /// `/* ... */` comments are ignored.
+    ///
+    /// A bare position between two colors is a transition hint; it moves the midpoint of
+    /// the transition to that position. [`LinearGradient`](crate::LinearGradient) renders
+    /// hints like browsers do, the other gradient types place the midpoint color there.
+    ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
@@ -165,10 +576,12 @@ impl GradientBuilder {
     /// # }
     /// ```
     pub fn css<'a>(&'a mut self, s: &str) -> &'a mut Self {
-        if let Some((colors, positions)) = css_gradient::parse(s, self.mode) {
+        if let Some((colors, positions, hints)) = css_gradient::parse(s) {
             self.invalid_css_gradient = false;
             self.colors = colors;
             self.positions = positions;
+            self.invalid_positions = false;
+            self.hints = hints;
         } else {
             self.invalid_css_gradient = true;
         }
@@ -180,8 +593,17 @@ impl GradientBuilder {
         self.colors.clear();
         self.positions.clear();
         self.mode = BlendMode::Rgb;
+        self.hints.clear();
+        self.position_curve = 1.0;
+        self.channel_modes = None;
+        self.hue_direction = HueDirection::Shorter;
+        self.reverse = false;
+        self.dedup_adjacent = false;
+        self.warn_non_monotone = false;
+        self.warnings.clear();
         self.invalid_html_colors.clear();
         self.invalid_css_gradient = false;
+        self.invalid_positions = false;
         self.clean = false;
         self
     }
@@ -196,6 +618,11 @@ impl GradientBuilder {
         &self.positions
     }
 
+    /// Get the warnings recorded by the last build
+    pub fn warnings(&self) -> &[GradientBuilderWarning] {
+        &self.warnings
+    }
+
     pub fn build<'a, T>(&'a mut self) -> Result<T, T::Error>
     where
         T: TryFrom<&'a mut Self, Error = GradientBuilderError>,
@@ -203,6 +630,33 @@ impl GradientBuilder {
         T::try_from(self)
     }
 
+    /// Build the gradient using the given blend mode, without changing the builder's blend
+    /// mode
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{BlendMode, Gradient, LinearGradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut gb = colorgrad::GradientBuilder::new();
+    /// gb.html_colors(&["#f00", "#00f"]);
+    ///
+    /// for mode in [BlendMode::Rgb, BlendMode::LinearRgb, BlendMode::Oklab] {
+    ///     let g = gb.build_with_mode::<LinearGradient>(mode)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_with_mode<T>(&mut self, mode: BlendMode) -> Result<T, GradientBuilderError>
+    where
+        T: for<'a> TryFrom<&'a mut Self, Error = GradientBuilderError>,
+    {
+        let prev = std::mem::replace(&mut self.mode, mode);
+        let res = T::try_from(self);
+        self.mode = prev;
+        res
+    }
+
     /// Build the gradient
     pub(crate) fn prepare_build(&mut self) -> Result<(), GradientBuilderError> {
         if self.clean {
@@ -219,6 +673,10 @@ impl GradientBuilder {
             return Err(GradientBuilderError::InvalidCssGradient);
         }
 
+        if self.invalid_positions {
+            return Err(GradientBuilderError::InvalidDomain);
+        }
+
         let colors = if self.colors.is_empty() {
             vec![
                 Color::new(0.0, 0.0, 0.0, 1.0),
@@ -230,8 +688,12 @@ impl GradientBuilder {
             self.colors.to_vec()
         };
 
+        if !(self.position_curve.is_finite() && self.position_curve > 0.0) {
+            return Err(GradientBuilderError::InvalidDomain);
+        }
+
         let positions = if self.positions.is_empty() {
-            linspace(0.0, 1.0, colors.len())
+            self.spaced_positions(0.0, 1.0, colors.len())
         } else if self.positions.len() == colors.len() {
             for p in self.positions.windows(2) {
                 if p[0] > p[1] {
@@ -243,13 +705,32 @@ impl GradientBuilder {
             if self.positions[0] >= self.positions[1] {
                 return Err(GradientBuilderError::InvalidDomain);
             }
-            linspace(self.positions[0], self.positions[1], colors.len())
+            self.spaced_positions(self.positions[0], self.positions[1], colors.len())
         } else {
             return Err(GradientBuilderError::InvalidDomain);
         };
 
+        let mut hints = self.hints.clone();
+        hints.resize(colors.len() - 1, None);
+
+        let (colors, positions) = if self.reverse {
+            let (dmin, dmax) = (positions[0], positions[positions.len() - 1]);
+            hints.reverse();
+            for h in hints.iter_mut().flatten() {
+                *h = 1.0 - *h;
+            }
+            self.reverse = false;
+            (
+                colors.into_iter().rev().collect::<Vec<_>>(),
+                positions.iter().rev().map(|p| dmin + dmax - p).collect(),
+            )
+        } else {
+            (colors, positions)
+        };
+
         self.colors.clear();
         self.positions.clear();
+        self.hints.clear();
 
         let mut prev = positions[0];
         let last_idx = positions.len() - 1;
@@ -262,13 +743,25 @@ impl GradientBuilder {
             };
             if (pos - prev) + (next - pos) < f32::EPSILON {
                 // skip
+            } else if self.dedup_adjacent
+                && self.colors.last() == Some(col)
+                && (i < last_idx || self.colors.len() > 1)
+            {
+                if i == last_idx {
+                    *self.positions.last_mut().unwrap() = *pos;
+                }
+                // the merged stop starts the next segment
+                *self.hints.last_mut().unwrap() = hints.get(i).copied().flatten();
             } else {
                 self.positions.push(*pos);
                 self.colors.push(col.clone());
+                self.hints.push(hints.get(i).copied().flatten());
             }
             prev = *pos;
         }
 
+        self.hints.pop();
+
         if self.colors.len() < 2 {
             return Err(GradientBuilderError::InvalidStops);
         }
@@ -276,4 +769,68 @@ impl GradientBuilder {
         self.clean = true;
         Ok(())
     }
+
+    // Record the warnings about the built gradient, the gradient type matters e.g. the
+    // splines can overshoot between stops with monotone lightness.
+    pub(crate) fn check_warnings(&mut self, grad: &dyn Gradient) {
+        self.warnings.clear();
+        if self.warn_non_monotone {
+            let lightness = grad
+                .colors(128)
+                .iter()
+                .map(|c| c.to_oklaba()[0])
+                .collect::<Vec<_>>();
+            if !is_monotone(&lightness) {
+                self.warnings
+                    .push(GradientBuilderWarning::NonMonotoneLightness);
+            }
+        }
+    }
+
+    fn spaced_positions(&self, min: f32, max: f32, n: usize) -> Vec<f32> {
+        if self.position_curve == 1.0 {
+            return linspace(min, max, n);
+        }
+        linspace(0.0, 1.0, n)
+            .iter()
+            .map(|t| min + t.powf(self.position_curve) * (max - min))
+            .collect()
+    }
+
+    // Colors and positions with a stop inserted at every interpolation hint, using the
+    // midpoint color of the transition. Used by gradients which don't support hints.
+    pub(crate) fn hints_as_stops(&self) -> (Vec<Color>, Vec<f32>) {
+        let mut colors = Vec::with_capacity(self.colors.len() * 2);
+        let mut positions = Vec::with_capacity(self.colors.len() * 2);
+
+        for (i, (col, pos)) in self.colors.iter().zip(&self.positions).enumerate() {
+            colors.push(col.clone());
+            positions.push(*pos);
+
+            if let Some(Some(hint)) = self.hints.get(i) {
+                let next = &self.colors[i + 1];
+                let col = match self.mode {
+                    BlendMode::Rgb => col.interpolate_rgb(next, 0.5),
+                    BlendMode::LinearRgb => col.interpolate_linear_rgb(next, 0.5),
+                    BlendMode::Oklab => col.interpolate_oklab(next, 0.5),
+                    #[cfg(feature = "lab")]
+                    BlendMode::Lab => col.interpolate_lab(next, 0.5),
+                    // hue based modes, in the hue direction
+                    _ => {
+                        let v = convert_colors(
+                            &[col.clone(), next.clone()],
+                            self.mode,
+                            self.hue_direction,
+                        )
+                        .collect::<Vec<_>>();
+                        to_color(&linear_interpolation(&v[0], &v[1], 0.5), self.mode)
+                    }
+                };
+                colors.push(col);
+                positions.push(pos + hint * (self.positions[i + 1] - pos));
+            }
+        }
+
+        (colors, positions)
+    }
 }
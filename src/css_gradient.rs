@@ -1,4 +1,4 @@
-use crate::{BlendMode, Color};
+use crate::Color;
 
 struct Stop {
     col: Option<Color>,
@@ -15,60 +15,63 @@ impl Stop {
     }
 }
 
-#[allow(clippy::question_mark)]
-pub(crate) fn parse(s: &str, mode: BlendMode) -> Option<(Vec<Color>, Vec<f32>)> {
-    let mut stops = Vec::new();
+// Returns the color stops, their positions and the interpolation hint of every
+// segment between two color stops. A hint is stored relative to its segment,
+// `0.5` (or `None`) being the plain linear transition.
+#[allow(clippy::type_complexity)]
+pub(crate) fn parse(s: &str) -> Option<(Vec<Color>, Vec<f32>, Vec<Option<f32>>)> {
+    let mut raw_stops = Vec::new();
+    let s = strip_comments(s);
 
-    for stop in split_by_comma(s) {
-        if !parse_stop(&mut stops, &split_by_space(stop)) {
+    for stop in split_by_comma(&s) {
+        if !parse_stop(&mut raw_stops, &split_by_space(stop)) {
             return None;
         }
     }
 
-    if stops.is_empty() {
+    if raw_stops.is_empty() {
         return None;
     }
 
-    if stops[0].col.is_none() {
+    if raw_stops[0].col.is_none() || raw_stops[raw_stops.len() - 1].col.is_none() {
         return None;
     }
 
-    for i in 0..stops.len() {
-        if i == 0 && stops[i].pos.is_none() {
-            stops[i].pos = Some(0.0);
-            continue;
-        }
+    // Separate the transition hints from the color stops
+    let mut stops: Vec<Stop> = Vec::with_capacity(raw_stops.len());
+    let mut hints = Vec::with_capacity(raw_stops.len());
 
-        if i == stops.len() - 1 {
-            if stops[i].pos.is_none() {
-                stops[i].pos = Some(1.0);
+    for stop in raw_stops {
+        if stop.col.is_some() {
+            if !stops.is_empty() && hints.len() < stops.len() {
+                hints.push(None);
             }
-            break;
+            stops.push(stop);
+        } else if hints.len() < stops.len() {
+            hints.push(stop.pos);
+        } else {
+            // two consecutive hints
+            return None;
         }
+    }
 
-        if stops[i].col.is_none() {
-            if stops[i + 1].col.is_none() {
-                return None;
-            }
-            let col1 = stops[i - 1].col.as_ref().unwrap();
-            let col2 = stops[i + 1].col.as_ref().unwrap();
-            let col = match mode {
-                BlendMode::Rgb => col1.interpolate_rgb(col2, 0.5),
-                BlendMode::LinearRgb => col1.interpolate_linear_rgb(col2, 0.5),
-                BlendMode::Oklab => col1.interpolate_oklab(col2, 0.5),
-                #[cfg(feature = "lab")]
-                BlendMode::Lab => col1.interpolate_lab(col2, 0.5),
-            };
-            stops[i].col = Some(col);
-        }
+    if stops[0].pos.is_none() {
+        stops[0].pos = Some(0.0);
+    }
+
+    let last = stops.len() - 1;
+    if stops[last].pos.is_none() {
+        stops[last].pos = Some(1.0);
     }
 
     if stops[0].pos.unwrap() > 0.0 {
         stops.insert(0, Stop::new(stops[0].col.clone(), Some(0.0)));
+        hints.insert(0, None);
     }
 
     if stops[stops.len() - 1].pos.unwrap() < 1.0 {
         stops.push(Stop::new(stops[stops.len() - 1].col.clone(), Some(1.0)));
+        hints.push(None);
     }
 
     for i in 0..stops.len() {
@@ -101,7 +104,19 @@ pub(crate) fn parse(s: &str, mode: BlendMode) -> Option<(Vec<Color>, Vec<f32>)>
         .iter()
         .map(|stop| stop.pos.unwrap())
         .collect::<Vec<_>>();
-    Some((colors, positions))
+    let hints = hints
+        .iter()
+        .zip(positions.windows(2))
+        .map(|(hint, p)| {
+            let hint = (*hint)?;
+            let len = p[1] - p[0];
+            if len < f32::EPSILON {
+                return None;
+            }
+            Some(((hint - p[0]) / len).clamp(0.0, 1.0))
+        })
+        .collect::<Vec<_>>();
+    Some((colors, positions, hints))
 }
 
 fn parse_stop(stops: &mut Vec<Stop>, stop: &[&str]) -> bool {
@@ -165,12 +180,30 @@ fn parse_pos(s: &str) -> Option<f32> {
         .or_else(|| s.parse().ok())
 }
 
+// Replace every `/* ... */` comment with a single space, like CSS does.
+// An unterminated comment runs until the end of the input.
+fn strip_comments(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("/*") {
+        res.push_str(&rest[..start]);
+        res.push(' ');
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => return res,
+        }
+    }
+    res.push_str(rest);
+    res
+}
+
 fn split_by_comma(s: &str) -> Vec<&str> {
     let mut res = Vec::new();
     let mut start = 0;
     let mut inside = false;
 
-    for (i, c) in s.chars().enumerate() {
+    for (i, c) in s.char_indices() {
         if c == ',' && !inside {
             res.push(&s[start..i]);
             start = i + 1;
@@ -189,7 +222,7 @@ fn split_by_space(s: &str) -> Vec<&str> {
     let mut start = 0;
     let mut inside = false;
 
-    for (i, c) in s.chars().enumerate() {
+    for (i, c) in s.char_indices() {
         if c == ' ' && !inside {
             if !s[start..i].is_empty() {
                 res.push(&s[start..i]);
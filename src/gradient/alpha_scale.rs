@@ -0,0 +1,41 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with every color's alpha multiplied by a constant factor.
+///
+/// Created by [`Gradient::scale_alpha`].
+#[derive(Clone)]
+pub struct AlphaScaleGradient {
+    gradient: Box<dyn Gradient>,
+    factor: f32,
+}
+
+impl AlphaScaleGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, factor: f32) -> Self {
+        Self {
+            gradient,
+            factor: factor.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl fmt::Debug for AlphaScaleGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AlphaScaleGradient")
+            .field("factor", &self.factor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for AlphaScaleGradient {
+    fn at(&self, t: f32) -> Color {
+        let mut c = self.gradient.at(t);
+        c.a *= self.factor;
+        c
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
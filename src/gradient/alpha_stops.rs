@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with every color's alpha multiplied by an alpha curve, defined independently
+/// of the color stops.
+///
+/// Created by [`Gradient::with_alpha_stops`].
+#[derive(Clone)]
+pub struct AlphaStopsGradient {
+    gradient: Box<dyn Gradient>,
+    stops: Vec<(f32, f32)>,
+}
+
+impl AlphaStopsGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, stops: &[(f32, f32)]) -> Self {
+        let mut stops = stops
+            .iter()
+            .filter(|(pos, _)| pos.is_finite())
+            .map(|&(pos, alpha)| (pos, alpha.clamp(0.0, 1.0)))
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { gradient, stops }
+    }
+
+    fn alpha_at(&self, t: f32) -> f32 {
+        if t.is_nan() {
+            return 1.0;
+        }
+
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 1.0,
+        };
+
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+
+        let i = self.stops.partition_point(|s| s.0 <= t);
+        let (p0, a0) = self.stops[i - 1];
+        let (p1, a1) = self.stops[i];
+        a0 + (t - p0) / (p1 - p0) * (a1 - a0)
+    }
+}
+
+impl fmt::Debug for AlphaStopsGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AlphaStopsGradient")
+            .field("stops", &self.stops)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for AlphaStopsGradient {
+    fn at(&self, t: f32) -> Color {
+        let mut c = self.gradient.at(t);
+        c.a *= self.alpha_at(t);
+        c
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
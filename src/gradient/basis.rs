@@ -1,6 +1,9 @@
 use std::convert::TryFrom;
 
-use crate::{convert_colors, BlendMode, Color, Gradient, GradientBuilder, GradientBuilderError};
+use crate::{
+    convert_colors, nearest_stop, to_color, BlendMode, Color, Gradient, GradientBuilder,
+    GradientBuilderError, HueDirection, SharpGradient,
+};
 
 // Basis spline algorithm adapted from:
 // https://github.com/d3/d3-interpolate/blob/master/src/basis.js
@@ -42,13 +45,18 @@ pub struct BasisGradient {
 }
 
 impl BasisGradient {
-    pub(crate) fn new(colors: &[Color], positions: Vec<f32>, mode: BlendMode) -> Self {
+    pub(crate) fn new(
+        colors: &[Color],
+        positions: Vec<f32>,
+        mode: BlendMode,
+        hue: HueDirection,
+    ) -> Self {
         let dmin = positions[0];
         let dmax = positions[positions.len() - 1];
         let first_color = colors[0].clone();
         let last_color = colors[colors.len() - 1].clone();
         Self {
-            values: convert_colors(colors, mode),
+            values: convert_colors(colors, mode, hue).collect(),
             positions,
             domain: (dmin, dmax),
             mode,
@@ -56,6 +64,20 @@ impl BasisGradient {
             last_color,
         }
     }
+
+    /// Get the color of the stop nearest to `t`, without interpolation.
+    pub fn snap_to_stop(&self, t: f32) -> Color {
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        let n = self.positions.len();
+        match nearest_stop(n, t, |i| self.positions[i]) {
+            0 => self.first_color.clone(),
+            i if i == n - 1 => self.last_color.clone(),
+            i => to_color(&self.values[i], self.mode),
+        }
+    }
 }
 
 impl Gradient for BasisGradient {
@@ -119,14 +141,25 @@ impl Gradient for BasisGradient {
             BlendMode::Rgb => Color::new(c0, c1, c2, c3),
             BlendMode::LinearRgb => Color::from_linear_rgba(c0, c1, c2, c3),
             BlendMode::Oklab => Color::from_oklaba(c0, c1, c2, c3),
+            BlendMode::Hsl => Color::from_hsla(c0, c1, c2, c3),
             #[cfg(feature = "lab")]
             BlendMode::Lab => Color::from_laba(c0, c1, c2, c3),
+            #[cfg(feature = "lab")]
+            BlendMode::Lch => Color::from_lcha(c0, c1, c2.to_radians(), c3),
         }
     }
 
     fn domain(&self) -> (f32, f32) {
         self.domain
     }
+
+    fn mode(&self) -> Option<BlendMode> {
+        Some(self.mode)
+    }
+
+    fn sharp(&self, segment: u16, smoothness: f32) -> SharpGradient {
+        self.sharp_in(segment, smoothness, self.mode)
+    }
 }
 
 impl TryFrom<&mut GradientBuilder> for BasisGradient {
@@ -134,6 +167,12 @@ impl TryFrom<&mut GradientBuilder> for BasisGradient {
 
     fn try_from(gb: &mut GradientBuilder) -> Result<Self, Self::Error> {
         gb.prepare_build()?;
-        Ok(Self::new(&gb.colors, gb.positions.clone(), gb.mode))
+        if gb.channel_modes.is_some() {
+            return Err(GradientBuilderError::UnsupportedChannelModes);
+        }
+        let (colors, positions) = gb.hints_as_stops();
+        let grad = Self::new(&colors, positions, gb.mode, gb.hue_direction);
+        gb.check_warnings(&grad);
+        Ok(grad)
     }
 }
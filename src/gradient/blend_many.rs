@@ -0,0 +1,83 @@
+use std::fmt;
+
+use crate::{convert_color, hue_channel, norm, to_color, BlendMode, Color, Gradient};
+
+/// Weighted blend of several gradients.
+///
+/// Created by [`blend_many`](crate::blend_many).
+#[derive(Clone)]
+pub struct BlendManyGradient {
+    // Gradients with their normalized weight
+    gradients: Vec<(Box<dyn Gradient>, f32)>,
+    mode: BlendMode,
+}
+
+impl BlendManyGradient {
+    pub(crate) fn new(gradients: &[(&dyn Gradient, f32)], mode: BlendMode) -> Self {
+        assert!(!gradients.is_empty(), "no gradients to blend");
+
+        let total = gradients.iter().map(|(_, w)| w.max(0.0)).sum::<f32>();
+        let n = gradients.len() as f32;
+
+        Self {
+            gradients: gradients
+                .iter()
+                .map(|(g, w)| {
+                    let w = if total > 0.0 {
+                        w.max(0.0) / total
+                    } else {
+                        1.0 / n
+                    };
+                    (g.clone_gradient(), w)
+                })
+                .collect(),
+            mode,
+        }
+    }
+}
+
+impl fmt::Debug for BlendManyGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlendManyGradient")
+            .field(
+                "weights",
+                &self.gradients.iter().map(|(_, w)| *w).collect::<Vec<_>>(),
+            )
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for BlendManyGradient {
+    fn at(&self, t: f32) -> Color {
+        let (dmin, dmax) = self.domain();
+        let t = norm(t, dmin, dmax);
+        let mut sum = [0.0; 4];
+        // Sum of the hue unit vectors, the mean of angles can't be computed linearly
+        let hue = hue_channel(self.mode);
+        let (mut hx, mut hy) = (0.0_f32, 0.0_f32);
+
+        for (g, w) in &self.gradients {
+            let (gmin, gmax) = g.domain();
+            let c = convert_color(&g.at(gmin + t * (gmax - gmin)), self.mode);
+            for (v, c) in sum.iter_mut().zip(c) {
+                *v += c * w;
+            }
+            if let Some(k) = hue {
+                let h = c[k].to_radians();
+                hx += h.cos() * w;
+                hy += h.sin() * w;
+            }
+        }
+
+        if let Some(k) = hue {
+            sum[k] = hy.atan2(hx).to_degrees();
+        }
+
+        to_color(&sum, self.mode)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradients[0].0.domain()
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with explicit colors for positions outside of the domain.
+///
+/// Created by [`Gradient::with_bounds`].
+#[derive(Clone)]
+pub struct BoundedGradient {
+    gradient: Box<dyn Gradient>,
+    under: Color,
+    over: Color,
+}
+
+impl BoundedGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, under: Color, over: Color) -> Self {
+        Self {
+            gradient,
+            under,
+            over,
+        }
+    }
+}
+
+impl fmt::Debug for BoundedGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundedGradient")
+            .field("under", &self.under)
+            .field("over", &self.over)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for BoundedGradient {
+    fn at(&self, t: f32) -> Color {
+        let (dmin, dmax) = self.gradient.domain();
+
+        if t < dmin {
+            return self.under.clone();
+        }
+
+        if t > dmax {
+            return self.over.clone();
+        }
+
+        self.gradient.at(t)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
@@ -1,6 +1,9 @@
 use std::convert::TryFrom;
 
-use crate::{convert_colors, BlendMode, Color, Gradient, GradientBuilder, GradientBuilderError};
+use crate::{
+    convert_colors, nearest_stop, to_color, BlendMode, Color, Gradient, GradientBuilder,
+    GradientBuilderError, HueDirection, SharpGradient,
+};
 
 // Catmull-Rom spline algorithm adapted from:
 // https://qroph.github.io/2018/07/30/smooth-paths-using-catmull-rom-splines.html
@@ -75,14 +78,19 @@ fn to_catmull_segments(values: &[f32]) -> Vec<[f32; 4]> {
 }
 
 impl CatmullRomGradient {
-    pub(crate) fn new(colors: &[Color], positions: Vec<f32>, mode: BlendMode) -> Self {
+    pub(crate) fn new(
+        colors: &[Color],
+        positions: Vec<f32>,
+        mode: BlendMode,
+        hue: HueDirection,
+    ) -> Self {
         let n = colors.len();
         let mut a = Vec::with_capacity(n);
         let mut b = Vec::with_capacity(n);
         let mut c = Vec::with_capacity(n);
         let mut d = Vec::with_capacity(n);
 
-        for col in convert_colors(colors, mode) {
+        for col in convert_colors(colors, mode, hue) {
             a.push(col[0]);
             b.push(col[1]);
             c.push(col[2]);
@@ -114,6 +122,23 @@ impl CatmullRomGradient {
             last_color,
         }
     }
+
+    /// Get the color of the stop nearest to `t`, without interpolation.
+    pub fn snap_to_stop(&self, t: f32) -> Color {
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        let n = self.positions.len();
+        match nearest_stop(n, t, |i| self.positions[i]) {
+            0 => self.first_color.clone(),
+            i if i == n - 1 => self.last_color.clone(),
+            i => {
+                let [a, b, c, d] = self.segments[i];
+                to_color(&[a[3], b[3], c[3], d[3]], self.mode)
+            }
+        }
+    }
 }
 
 impl Gradient for CatmullRomGradient {
@@ -163,14 +188,25 @@ impl Gradient for CatmullRomGradient {
             BlendMode::Rgb => Color::new(c0, c1, c2, c3),
             BlendMode::LinearRgb => Color::from_linear_rgba(c0, c1, c2, c3),
             BlendMode::Oklab => Color::from_oklaba(c0, c1, c2, c3),
+            BlendMode::Hsl => Color::from_hsla(c0, c1, c2, c3),
             #[cfg(feature = "lab")]
             BlendMode::Lab => Color::from_laba(c0, c1, c2, c3),
+            #[cfg(feature = "lab")]
+            BlendMode::Lch => Color::from_lcha(c0, c1, c2.to_radians(), c3),
         }
     }
 
     fn domain(&self) -> (f32, f32) {
         self.domain
     }
+
+    fn mode(&self) -> Option<BlendMode> {
+        Some(self.mode)
+    }
+
+    fn sharp(&self, segment: u16, smoothness: f32) -> SharpGradient {
+        self.sharp_in(segment, smoothness, self.mode)
+    }
 }
 
 impl TryFrom<&mut GradientBuilder> for CatmullRomGradient {
@@ -178,6 +214,12 @@ impl TryFrom<&mut GradientBuilder> for CatmullRomGradient {
 
     fn try_from(gb: &mut GradientBuilder) -> Result<Self, Self::Error> {
         gb.prepare_build()?;
-        Ok(Self::new(&gb.colors, gb.positions.clone(), gb.mode))
+        if gb.channel_modes.is_some() {
+            return Err(GradientBuilderError::UnsupportedChannelModes);
+        }
+        let (colors, positions) = gb.hints_as_stops();
+        let grad = Self::new(&colors, positions, gb.mode, gb.hue_direction);
+        gb.check_warnings(&grad);
+        Ok(grad)
     }
 }
@@ -0,0 +1,37 @@
+use std::fmt;
+
+use crate::{channel_value, Channel, Color, Gradient};
+
+/// Grayscale gradient of a single channel of another gradient.
+///
+/// Created by [`Gradient::channel`].
+#[derive(Clone)]
+pub struct ChannelGradient {
+    gradient: Box<dyn Gradient>,
+    channel: Channel,
+}
+
+impl ChannelGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, channel: Channel) -> Self {
+        Self { gradient, channel }
+    }
+}
+
+impl fmt::Debug for ChannelGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ChannelGradient")
+            .field("channel", &self.channel)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for ChannelGradient {
+    fn at(&self, t: f32) -> Color {
+        let v = channel_value(&self.gradient.at(t), self.channel);
+        Color::new(v, v, v, 1.0)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with every color's lightness clamped into a range in Oklab color space,
+/// keeping its hue and chroma.
+///
+/// Created by [`Gradient::clamp_lightness`].
+#[derive(Clone)]
+pub struct ClampLightnessGradient {
+    gradient: Box<dyn Gradient>,
+    min: f32,
+    max: f32,
+}
+
+impl ClampLightnessGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, l_min: f32, l_max: f32) -> Self {
+        let (min, max) = if l_min <= l_max {
+            (l_min, l_max)
+        } else {
+            (l_max, l_min)
+        };
+        Self {
+            gradient,
+            min: min.clamp(0.0, 1.0),
+            max: max.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl fmt::Debug for ClampLightnessGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClampLightnessGradient")
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for ClampLightnessGradient {
+    fn at(&self, t: f32) -> Color {
+        let [l, a, b, alpha] = self.gradient.at(t).to_oklaba();
+        Color::from_oklaba(l.clamp(self.min, self.max), a, b, alpha)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
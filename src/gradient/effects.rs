@@ -0,0 +1,157 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Color, Gradient};
+
+#[derive(Clone)]
+enum Effect {
+    // Position effects
+    Invert,
+    Gamma(f32),
+    Warp(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+    // Color effects
+    ShiftHue(f32),
+    ScaleAlpha(f32),
+    Clamp,
+}
+
+impl fmt::Debug for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Invert => f.write_str("Invert"),
+            Self::Gamma(v) => f.debug_tuple("Gamma").field(v).finish(),
+            Self::Warp(_) => f.write_str("Warp"),
+            Self::ShiftHue(v) => f.debug_tuple("ShiftHue").field(&v.to_degrees()).finish(),
+            Self::ScaleAlpha(v) => f.debug_tuple("ScaleAlpha").field(v).finish(),
+            Self::Clamp => f.write_str("Clamp"),
+        }
+    }
+}
+
+/// Gradient applying a chain of effects, in order, to another gradient.
+///
+/// Created by [`Gradient::effects`]. Applying the effects using a single gradient avoids
+/// nesting a wrapper gradient for each effect.
+///
+/// ```
+/// use colorgrad::Gradient;
+///
+/// let g = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#f00", "#00f"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap()
+///     .effects()
+///     .invert()
+///     .scale_alpha(0.5);
+///
+/// assert_eq!(g.at(0.0).to_css_hex(), "#0000ff80");
+/// assert_eq!(g.at(1.0).to_css_hex(), "#ff000080");
+/// ```
+#[derive(Clone)]
+pub struct GradientEffects {
+    gradient: Box<dyn Gradient>,
+    effects: Vec<Effect>,
+}
+
+impl GradientEffects {
+    pub(crate) fn new(gradient: Box<dyn Gradient>) -> Self {
+        Self {
+            gradient,
+            effects: Vec::new(),
+        }
+    }
+
+    fn push(mut self, effect: Effect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Reverse the positions across the domain
+    ///
+    /// Inverting twice in a row cancels out exactly, no effect is added.
+    pub fn invert(mut self) -> Self {
+        if let Some(Effect::Invert) = self.effects.last() {
+            self.effects.pop();
+            return self;
+        }
+        self.push(Effect::Invert)
+    }
+
+    /// Remap the normalized position `t` to `t.powf(gamma)`
+    pub fn gamma(self, gamma: f32) -> Self {
+        self.push(Effect::Gamma(gamma))
+    }
+
+    /// Remap the normalized position using `f`, which maps `[0, 1]` to `[0, 1]`
+    pub fn warp<F>(self, f: F) -> Self
+    where
+        F: Fn(f32) -> f32 + Send + Sync + 'static,
+    {
+        self.push(Effect::Warp(Arc::new(f)))
+    }
+
+    /// Rotate the hue of every color by `degrees` in Oklch color space, see
+    /// [`Gradient::shift_hue`]
+    pub fn shift_hue(self, degrees: f32) -> Self {
+        self.push(Effect::ShiftHue((degrees % 360.0).to_radians()))
+    }
+
+    /// Multiply the alpha of every color by `factor`, see [`Gradient::scale_alpha`]
+    pub fn scale_alpha(self, factor: f32) -> Self {
+        self.push(Effect::ScaleAlpha(factor.clamp(0.0, 1.0)))
+    }
+
+    /// Clamp every color to the sRGB gamut
+    pub fn clamp(self) -> Self {
+        self.push(Effect::Clamp)
+    }
+}
+
+impl fmt::Debug for GradientEffects {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GradientEffects")
+            .field("effects", &self.effects)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for GradientEffects {
+    fn at(&self, t: f32) -> Color {
+        let (dmin, dmax) = self.gradient.domain();
+        let mut t = t;
+
+        // Like nested wrappers, the last position effect is the first one to remap t.
+        for effect in self.effects.iter().rev() {
+            let u = || ((t - dmin) / (dmax - dmin)).clamp(0.0, 1.0);
+            t = match effect {
+                Effect::Invert => dmin + dmax - t,
+                Effect::Gamma(gamma) => dmin + u().powf(*gamma) * (dmax - dmin),
+                Effect::Warp(f) => dmin + f(u()) * (dmax - dmin),
+                _ => t,
+            };
+        }
+
+        let mut col = self.gradient.at(t);
+
+        for effect in &self.effects {
+            col = match effect {
+                Effect::ShiftHue(shift) => {
+                    let [l, c, h, a] = col.to_oklcha();
+                    Color::from_oklcha(l, c, h + shift, a)
+                }
+                Effect::ScaleAlpha(factor) => {
+                    col.a *= factor;
+                    col
+                }
+                Effect::Clamp => col.clamp(),
+                _ => col,
+            };
+        }
+
+        col
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
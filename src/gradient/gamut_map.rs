@@ -0,0 +1,98 @@
+use std::fmt;
+
+use crate::{oklab_distance, Color, Gradient, JND};
+
+// CSS Color 4 gamut mapping algorithm:
+// https://www.w3.org/TR/css-color-4/#css-gamut-mapping
+
+const EPSILON: f32 = 0.0001;
+
+/// Gradient with every out of sRGB gamut color mapped into the gamut by reducing its
+/// chroma in Oklch color space, keeping its lightness and hue.
+///
+/// Created by [`Gradient::gamut_map_oklch`].
+#[derive(Clone)]
+pub struct GamutMapGradient {
+    gradient: Box<dyn Gradient>,
+}
+
+impl GamutMapGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>) -> Self {
+        Self { gradient }
+    }
+}
+
+impl fmt::Debug for GamutMapGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GamutMapGradient").finish_non_exhaustive()
+    }
+}
+
+impl Gradient for GamutMapGradient {
+    fn at(&self, t: f32) -> Color {
+        gamut_map(&self.gradient.at(t))
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
+
+fn gamut_map(col: &Color) -> Color {
+    if in_gamut(col) {
+        return col.clone();
+    }
+
+    let [l, c, h, alpha] = col.to_oklcha();
+
+    if l >= 1.0 {
+        return Color::new(1.0, 1.0, 1.0, alpha);
+    }
+
+    if l <= 0.0 {
+        return Color::new(0.0, 0.0, 0.0, alpha);
+    }
+
+    let mut min = 0.0;
+    let mut max = c;
+    let mut min_in_gamut = true;
+    let mut current = col.clone();
+    let mut clipped = current.clamp();
+
+    if oklab_distance(&clipped.to_oklaba(), &current.to_oklaba()) < JND {
+        return clipped;
+    }
+
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        current = Color::from_oklcha(l, chroma, h, alpha);
+
+        if min_in_gamut && in_gamut(&current) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = current.clamp();
+        let e = oklab_distance(&clipped.to_oklaba(), &current.to_oklaba());
+
+        if e < JND {
+            if JND - e < EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+#[inline]
+fn in_gamut(col: &Color) -> bool {
+    const E: f32 = 1e-5;
+    [col.r, col.g, col.b]
+        .iter()
+        .all(|v| (-E..=1.0 + E).contains(v))
+}
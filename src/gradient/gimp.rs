@@ -98,6 +98,38 @@ impl GimpGradient {
         parse_ggr(r, foreground, background)
     }
 
+    /// Parse GIMP gradient from a string
+    ///
+    /// ```
+    /// use colorgrad::{Color, GimpGradient, Gradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ggr = "GIMP Gradient\nName: My Gradient\n1\n0 0.5 1 0 0 0 1 1 1 1 1 0 0 0 0";
+    /// let col = Color::default();
+    /// let grad = GimpGradient::from_str(ggr, &col, &col)?;
+    ///
+    /// assert_eq!(grad.name(), "My Gradient");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(
+        s: &str,
+        foreground: &Color,
+        background: &Color,
+    ) -> Result<Self, ParseGgrError> {
+        Self::new(s.as_bytes(), foreground, background)
+    }
+
+    /// Parse GIMP gradient from bytes
+    pub fn from_bytes(
+        b: &[u8],
+        foreground: &Color,
+        background: &Color,
+    ) -> Result<Self, ParseGgrError> {
+        Self::new(b, foreground, background)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -158,6 +190,11 @@ impl Gradient for GimpGradient {
                             return Color::from_hsva(h, s, v, a);
                         }
                     }
+                } else if pos <= 0.0 {
+                    // log10(0) is -inf
+                    0.0
+                } else if pos >= 1.0 {
+                    1.0
                 } else {
                     (-LN_2 * pos.log10() / middle.log10()).exp()
                 }
@@ -396,11 +433,28 @@ fn parse_segment(s: &str, foreground: &Color, background: &Color) -> Option<Gimp
     })
 }
 
+// Hues in [0, 360), so that identical-looking hues (e.g. 0 and 360) compare equal and
+// don't sweep the whole color wheel.
+fn normalize_hues(h1: f32, h2: f32) -> (f32, f32) {
+    let norm = |h: f32| {
+        let h = h.rem_euclid(360.0);
+        if h >= 360.0 {
+            0.0
+        } else {
+            h
+        }
+    };
+    (norm(h1), norm(h2))
+}
+
 fn blend_hsv_ccw(c1: &[f32; 4], c2: &[f32; 4], t: f32) -> Color {
-    let [h1, s1, v1, a1] = c1;
-    let [h2, s2, v2, a2] = c2;
+    let [_, s1, v1, a1] = c1;
+    let [_, s2, v2, a2] = c2;
+    let (h1, h2) = normalize_hues(c1[0], c2[0]);
 
-    let hue = if h1 < h2 {
+    let hue = if h1 == h2 {
+        h1
+    } else if h1 < h2 {
         h1 + ((h2 - h1) * t)
     } else {
         let h = h1 + ((360.0 - (h1 - h2)) * t);
@@ -421,10 +475,13 @@ fn blend_hsv_ccw(c1: &[f32; 4], c2: &[f32; 4], t: f32) -> Color {
 }
 
 fn blend_hsv_cw(c1: &[f32; 4], c2: &[f32; 4], t: f32) -> Color {
-    let [h1, s1, v1, a1] = c1;
-    let [h2, s2, v2, a2] = c2;
+    let [_, s1, v1, a1] = c1;
+    let [_, s2, v2, a2] = c2;
+    let (h1, h2) = normalize_hues(c1[0], c2[0]);
 
-    let hue = if h2 < h1 {
+    let hue = if h1 == h2 {
+        h1
+    } else if h2 < h1 {
         h1 - ((h1 - h2) * t)
     } else {
         let h = h1 - ((360.0 - (h2 - h1)) * t);
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with every color's hue rotated in Oklch color space.
+///
+/// Created by [`Gradient::shift_hue`].
+#[derive(Clone)]
+pub struct HueShiftGradient {
+    gradient: Box<dyn Gradient>,
+    // Hue offset in radians
+    shift: f32,
+}
+
+impl HueShiftGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, degrees: f32) -> Self {
+        Self {
+            gradient,
+            shift: (degrees % 360.0).to_radians(),
+        }
+    }
+}
+
+impl fmt::Debug for HueShiftGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HueShiftGradient")
+            .field("degrees", &self.shift.to_degrees())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for HueShiftGradient {
+    fn at(&self, t: f32) -> Color {
+        let [l, c, h, a] = self.gradient.at(t).to_oklcha();
+        Color::from_oklcha(l, c, h + self.shift, a)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
@@ -1,6 +1,15 @@
 use std::convert::TryFrom;
 
-use crate::{convert_colors, BlendMode, Color, Gradient, GradientBuilder, GradientBuilderError};
+use crate::{
+    convert_colors, hue_delta, nearest_stop, to_color, BlendMode, ChannelMode, Color, Gradient,
+    GradientBuilder, GradientBuilderError, HueDirection, SharpGradient,
+};
+
+#[cfg(feature = "smallvec")]
+type Stops = smallvec::SmallVec<[(f32, [f32; 4]); 8]>;
+
+#[cfg(not(feature = "smallvec"))]
+type Stops = Vec<(f32, [f32; 4])>;
 
 #[cfg_attr(
     feature = "named-colors",
@@ -19,81 +28,407 @@ let grad = colorgrad::GradientBuilder::new()
 )]
 #[derive(Debug, Clone)]
 pub struct LinearGradient {
-    stops: Vec<(f32, [f32; 4])>,
+    stops: Stops,
+    // Interpolation hint exponent of each segment, empty if there are no hints
+    hints: Vec<Option<f32>>,
     domain: (f32, f32),
     mode: BlendMode,
+    // Per channel interpolation in HSL, overrides `mode`
+    channel_modes: Option<[ChannelMode; 3]>,
+    hue_direction: HueDirection,
     first_color: Color,
     last_color: Color,
 }
 
 impl LinearGradient {
-    pub(crate) fn new(colors: &[Color], positions: &[f32], mode: BlendMode) -> Self {
+    pub(crate) fn new(
+        colors: &[Color],
+        positions: &[f32],
+        hints: &[Option<f32>],
+        mode: BlendMode,
+        channel_modes: Option<[ChannelMode; 3]>,
+        hue_direction: HueDirection,
+    ) -> Self {
         let dmin = positions[0];
         let dmax = positions[positions.len() - 1];
         let first_color = colors[0].clone();
         let last_color = colors[colors.len() - 1].clone();
-        let colors = convert_colors(colors, mode);
-        Self {
-            stops: positions
+        let stops = if channel_modes.is_some() {
+            positions
+                .iter()
+                .copied()
+                .zip(colors.iter().map(|c| c.to_hsla()))
+                .collect()
+        } else {
+            positions
                 .iter()
-                .zip(colors.iter())
-                .map(|(p, c)| (*p, *c))
-                .collect(),
+                .copied()
+                .zip(convert_colors(colors, mode, hue_direction))
+                .collect()
+        };
+        Self {
+            stops,
+            hints: if hints.iter().any(Option::is_some) {
+                hints.iter().map(|h| h.and_then(hint_exponent)).collect()
+            } else {
+                Vec::new()
+            },
             domain: (dmin, dmax),
             mode,
+            channel_modes,
+            hue_direction,
             first_color,
             last_color,
         }
     }
-}
 
-impl Gradient for LinearGradient {
-    fn at(&self, t: f32) -> Color {
-        if t <= self.domain.0 {
-            return self.first_color.clone();
-        }
+    /// Get the average color of the gradient across its domain.
+    ///
+    /// The average is taken in the blending color space: the mean of each segment,
+    /// weighted by the segment width. Circular channels (see [`ChannelMode::Circular`])
+    /// are averaged as angles, the mean of a segment with an interpolation hint is then
+    /// approximated. The hue of [`BlendMode::Hsl`] and `BlendMode::Lch` is blended like
+    /// the other channels and averaged the same way.
+    pub fn average_color(&self) -> Color {
+        let mut sum = [0.0; 4];
+        // Sum of the unit vectors of the circular channels, the mean of angles can't be
+        // computed linearly
+        let (mut hx, mut hy) = ([0.0_f32; 3], [0.0_f32; 3]);
 
-        if t >= self.domain.1 {
-            return self.last_color.clone();
-        }
+        for (i, s) in self.stops.windows(2).enumerate() {
+            let (pos_0, col_0) = s[0];
+            let (pos_1, col_1) = s[1];
 
-        if t.is_nan() {
-            return Color::new(0.0, 0.0, 0.0, 1.0);
+            // mean of t^e over [0, 1]
+            let w = match self.hints.get(i) {
+                Some(Some(e)) => 1.0 / (e + 1.0),
+                _ => 0.5,
+            };
+
+            let mean = if let Some(modes) = self.channel_modes {
+                channel_interpolation(&col_0, &col_1, w, &modes, self.hue_direction)
+            } else {
+                linear_interpolation(&col_0, &col_1, w)
+            };
+
+            for (v, m) in sum.iter_mut().zip(mean) {
+                *v += m * (pos_1 - pos_0);
+            }
+
+            if let Some(modes) = self.channel_modes {
+                for i in 0..3 {
+                    if modes[i] != ChannelMode::Circular {
+                        continue;
+                    }
+                    // the mean of the unit vectors along an arc is shorter than one
+                    let half = hue_delta(col_0[i], col_1[i], self.hue_direction).to_radians() / 2.0;
+                    let arc = if half == 0.0 { 1.0 } else { half.sin() / half };
+                    let h = mean[i].to_radians();
+                    hx[i] += h.cos() * arc * (pos_1 - pos_0);
+                    hy[i] += h.sin() * arc * (pos_1 - pos_0);
+                }
+            }
         }
 
-        let mut low = 0;
-        let mut high = self.stops.len();
+        let width = self.domain.1 - self.domain.0;
+        let [a, b, c, d] = sum.map(|v| v / width);
 
-        while low < high {
-            let mid = (low + high) / 2;
-            if self.stops[mid].0 < t {
-                low = mid + 1;
-            } else {
-                high = mid;
+        if let Some(modes) = self.channel_modes {
+            let mut v = [a, b, c, d];
+            for (i, mode) in modes.iter().enumerate() {
+                if *mode == ChannelMode::Circular {
+                    v[i] = hy[i].atan2(hx[i]).to_degrees().rem_euclid(360.0);
+                }
             }
+            return Color::from_hsla(v[0], v[1], v[2], v[3]);
         }
 
-        if low == 0 {
-            low = 1;
+        to_color(&[a, b, c, d], self.mode)
+    }
+
+    /// Get a sampler which is faster than [`at`](Gradient::at) for increasing positions,
+    /// e.g. when filling an image row by row.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// let mut sampler = grad.scanline_sampler();
+    /// let row = (0..100)
+    ///     .map(|x| sampler.at(x as f32 / 100.0).to_rgba8())
+    ///     .collect::<Vec<_>>();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scanline_sampler(&self) -> ScanlineSampler<'_> {
+        ScanlineSampler {
+            gradient: self,
+            low: 1,
+            last: f32::NEG_INFINITY,
         }
+    }
+
+    /// Get the colors of non-decreasing positions `ts` into `out`, walking the stops once
+    /// instead of searching the segment of every position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len() != ts.len()`. In debug builds, also panics if `ts` is not
+    /// sorted.
+    pub fn at_sorted(&self, ts: &[f32], out: &mut [Color]) {
+        assert_eq!(ts.len(), out.len(), "ts and out lengths differ");
+        debug_assert!(
+            ts.windows(2)
+                .all(|w| w[0] <= w[1] || w[0].is_nan() || w[1].is_nan()),
+            "ts is not sorted"
+        );
+
+        let mut sampler = self.scanline_sampler();
+        for (t, col) in ts.iter().zip(out.iter_mut()) {
+            *col = sampler.at(*t);
+        }
+    }
 
+    // Color at t, which is inside the domain, between the stops low - 1 and low.
+    fn at_segment(&self, low: usize, t: f32) -> Color {
         let (pos_0, col_0) = self.stops[low - 1];
         let (pos_1, col_1) = self.stops[low];
-        let t = (t - pos_0) / (pos_1 - pos_0);
+        let mut t = (t - pos_0) / (pos_1 - pos_0);
+
+        if let Some(Some(e)) = self.hints.get(low - 1) {
+            t = t.powf(*e);
+        }
+
+        if let Some(modes) = self.channel_modes {
+            let [h, s, l, a] = channel_interpolation(&col_0, &col_1, t, &modes, self.hue_direction);
+            return Color::from_hsla(h, s, l, a);
+        }
+
         let [a, b, c, d] = linear_interpolation(&col_0, &col_1, t);
 
         match self.mode {
             BlendMode::Rgb => Color::new(a, b, c, d),
             BlendMode::LinearRgb => Color::from_linear_rgba(a, b, c, d),
             BlendMode::Oklab => Color::from_oklaba(a, b, c, d),
+            BlendMode::Hsl => Color::from_hsla(a, b, c, d),
             #[cfg(feature = "lab")]
             BlendMode::Lab => Color::from_laba(a, b, c, d),
+            #[cfg(feature = "lab")]
+            BlendMode::Lch => Color::from_lcha(a, b, c.to_radians(), d),
+        }
+    }
+
+    /// Get the color of the stop nearest to `t`, without interpolation.
+    pub fn snap_to_stop(&self, t: f32) -> Color {
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        self.stop_color(nearest_stop(self.stops.len(), t, |i| self.stops[i].0))
+    }
+
+    /// Get every segment between two adjacent stops as `(pos_0, color_0, pos_1, color_1)`,
+    /// useful for segment based rendering.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// for (pos_0, col_0, pos_1, col_1) in grad.segments() {
+    ///     // draw a transition from col_0 to col_1
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> Vec<(f32, Color, f32, Color)> {
+        (1..self.stops.len())
+            .map(|i| {
+                (
+                    self.stops[i - 1].0,
+                    self.stop_color(i - 1),
+                    self.stops[i].0,
+                    self.stop_color(i),
+                )
+            })
+            .collect()
+    }
+
+    /// Get new hard-edge gradient with one band per stop, using exactly the stop colors,
+    /// regardless of their positions.
+    ///
+    /// Unlike [`sharp`](Gradient::sharp), which samples the gradient, the band colors are
+    /// the stop colors. The smooth transitions between bands are blended using the
+    /// gradient's blend mode.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .domain(&[0.0, 0.1, 1.0])
+    ///     .build::<colorgrad::LinearGradient>()?
+    ///     .sharp_stops(0.0);
+    ///
+    /// assert_eq!(grad.at(0.5).to_css_hex(), "#00ff00");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sharp_stops(&self, smoothness: f32) -> SharpGradient {
+        let colors = (0..self.stops.len())
+            .map(|i| self.stop_color(i))
+            .collect::<Vec<_>>();
+        SharpGradient::new(&colors, self.domain, smoothness, self.mode)
+    }
+
+    /// Get new gradient with the stop `index` moved to `new_pos`, clamped to stay strictly
+    /// between its neighbors, for interactive editors.
+    ///
+    /// Moving the first or the last stop changes the domain. A NaN or infinite position
+    /// leaves the stop unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let grad = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0f0", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?
+    ///     .move_stop(1, 0.25);
+    ///
+    /// assert_eq!(grad.at(0.25).to_css_hex(), "#00ff00");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_stop(&self, index: usize, new_pos: f32) -> LinearGradient {
+        assert!(index < self.stops.len(), "stop index out of bounds");
+
+        let mut grad = self.clone();
+        if !new_pos.is_finite() {
+            return grad;
+        }
+
+        let lo = index.checked_sub(1).map(|i| self.stops[i].0);
+        let hi = self.stops.get(index + 1).map(|s| s.0);
+        // smallest gap keeping the positions distinct
+        let gap = |a: f32, b: f32| f32::EPSILON * a.abs().max(b.abs()).max(1.0);
+
+        let pos = match (lo, hi) {
+            (Some(lo), Some(hi)) if hi - lo <= 2.0 * gap(lo, hi) => (lo + hi) / 2.0,
+            (Some(lo), Some(hi)) => new_pos.clamp(lo + gap(lo, hi), hi - gap(lo, hi)),
+            (Some(lo), None) => new_pos.max(lo + gap(lo, new_pos)),
+            (None, Some(hi)) => new_pos.min(hi - gap(new_pos, hi)),
+            (None, None) => new_pos,
+        };
+
+        grad.stops[index].0 = pos;
+        grad.domain = (grad.stops[0].0, grad.stops[grad.stops.len() - 1].0);
+        grad
+    }
+
+    fn stop_color(&self, i: usize) -> Color {
+        if i == 0 {
+            return self.first_color.clone();
+        }
+
+        if i == self.stops.len() - 1 {
+            return self.last_color.clone();
         }
+
+        if self.channel_modes.is_some() {
+            let [h, s, l, a] = self.stops[i].1;
+            return Color::from_hsla(h, s, l, a);
+        }
+
+        to_color(&self.stops[i].1, self.mode)
+    }
+}
+
+impl Gradient for LinearGradient {
+    fn at(&self, t: f32) -> Color {
+        if t <= self.domain.0 {
+            return self.first_color.clone();
+        }
+
+        if t >= self.domain.1 {
+            return self.last_color.clone();
+        }
+
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        self.at_segment(search(&self.stops, t), t)
     }
 
     fn domain(&self) -> (f32, f32) {
         self.domain
     }
+
+    fn mode(&self) -> Option<BlendMode> {
+        // the channel modes override the blend mode
+        if self.channel_modes.is_some() {
+            return None;
+        }
+        Some(self.mode)
+    }
+
+    fn sharp(&self, segment: u16, smoothness: f32) -> SharpGradient {
+        self.sharp_in(segment, smoothness, self.mode)
+    }
+}
+
+/// Sampler of [`LinearGradient`] which remembers the last segment.
+///
+/// Created by [`LinearGradient::scanline_sampler`]. Successive increasing positions only
+/// advance from the last segment instead of doing a binary search every time.
+#[derive(Debug, Clone)]
+pub struct ScanlineSampler<'a> {
+    gradient: &'a LinearGradient,
+    low: usize,
+    last: f32,
+}
+
+impl ScanlineSampler<'_> {
+    /// Get color at certain position, the same as [`LinearGradient`]'s [`at`](Gradient::at)
+    pub fn at(&mut self, t: f32) -> Color {
+        let g = self.gradient;
+
+        if t <= g.domain.0 {
+            return g.first_color.clone();
+        }
+
+        if t >= g.domain.1 {
+            return g.last_color.clone();
+        }
+
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        if t < self.last {
+            self.low = search(&g.stops, t);
+        } else {
+            while g.stops[self.low].0 < t {
+                self.low += 1;
+            }
+        }
+
+        self.last = t;
+        g.at_segment(self.low, t)
+    }
 }
 
 impl TryFrom<&mut GradientBuilder> for LinearGradient {
@@ -101,10 +436,73 @@ impl TryFrom<&mut GradientBuilder> for LinearGradient {
 
     fn try_from(gb: &mut GradientBuilder) -> Result<Self, Self::Error> {
         gb.prepare_build()?;
-        Ok(Self::new(&gb.colors, &gb.positions, gb.mode))
+        let grad = Self::new(
+            &gb.colors,
+            &gb.positions,
+            &gb.hints,
+            gb.mode,
+            gb.channel_modes,
+            gb.hue_direction,
+        );
+        gb.check_warnings(&grad);
+        Ok(grad)
+    }
+}
+
+// Index of the stop at the end of the segment containing t.
+pub(crate) fn search(stops: &[(f32, [f32; 4])], t: f32) -> usize {
+    let mut low = 0;
+    let mut high = stops.len();
+
+    while low < high {
+        let mid = (low + high) / 2;
+        if stops[mid].0 < t {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == 0 {
+        low = 1;
+    }
+
+    low
+}
+
+// Exponent which moves the midpoint of a transition to the relative position `hint`,
+// see https://www.w3.org/TR/css-images-4/#coloring-gradient-line
+fn hint_exponent(hint: f32) -> Option<f32> {
+    if hint <= 0.0 {
+        Some(0.0)
+    } else if hint >= 1.0 {
+        Some(f32::INFINITY)
+    } else if (hint - 0.5).abs() < f32::EPSILON {
+        None
+    } else {
+        Some(0.5_f32.ln() / hint.ln())
     }
 }
 
+#[inline]
+fn channel_interpolation(
+    a: &[f32; 4],
+    b: &[f32; 4],
+    t: f32,
+    modes: &[ChannelMode; 3],
+    hue: HueDirection,
+) -> [f32; 4] {
+    let mut res = linear_interpolation(a, b, t);
+
+    for (i, mode) in modes.iter().enumerate() {
+        if *mode == ChannelMode::Circular {
+            res[i] = (a[i] + t * hue_delta(a[i], b[i], hue)).rem_euclid(360.0);
+        }
+    }
+
+    res
+}
+
 #[inline]
 pub(crate) fn linear_interpolation(a: &[f32; 4], b: &[f32; 4], t: f32) -> [f32; 4] {
     [
@@ -1,7 +1,19 @@
+pub(crate) mod alpha_scale;
+pub(crate) mod alpha_stops;
 pub(crate) mod basis;
+pub(crate) mod blend_many;
+pub(crate) mod bounded;
 pub(crate) mod catmull_rom;
+pub(crate) mod channel;
+pub(crate) mod clamp_lightness;
+pub(crate) mod effects;
+pub(crate) mod gamut_map;
+pub(crate) mod hue_shift;
 pub(crate) mod linear;
+pub(crate) mod nan_color;
+pub(crate) mod shared;
 pub(crate) mod sharp;
+pub(crate) mod smoothstep;
 
 #[cfg(feature = "preset")]
 pub mod preset;
@@ -0,0 +1,40 @@
+use std::fmt;
+
+use crate::{Color, Gradient};
+
+/// Gradient with an explicit color for NaN positions, e.g. for missing data.
+///
+/// Created by [`Gradient::with_nan_color`].
+#[derive(Clone)]
+pub struct NanColorGradient {
+    gradient: Box<dyn Gradient>,
+    color: Color,
+}
+
+impl NanColorGradient {
+    pub(crate) fn new(gradient: Box<dyn Gradient>, color: Color) -> Self {
+        Self { gradient, color }
+    }
+}
+
+impl fmt::Debug for NanColorGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NanColorGradient")
+            .field("color", &self.color)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gradient for NanColorGradient {
+    fn at(&self, t: f32) -> Color {
+        if t.is_nan() {
+            return self.color.clone();
+        }
+
+        self.gradient.at(t)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
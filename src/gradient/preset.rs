@@ -7,12 +7,12 @@
 //! assert_eq!(grad.domain(), (0.0, 1.0)); // all preset gradients are in the domain [0..1]
 //! assert_eq!(grad.at(0.25).to_rgba8(), [255, 94, 99, 255]);
 //! assert_eq!(grad.at(0.75).to_rgba8(), [77, 199, 194, 255]);
-//! assert_eq!(grad.at(0.37).to_hex_string(), "#f2a42f");
+//! assert_eq!(grad.at(0.37).to_css_hex(), "#f2a42f");
 //! ```
 
 use std::f32::consts::{FRAC_PI_3, PI};
 
-use crate::{linspace, BasisGradient, BlendMode, Color, Gradient};
+use crate::{linspace, BasisGradient, BlendMode, Color, Gradient, HueDirection, LinearGradient};
 
 const PI2_3: f32 = PI * 2.0 / 3.0;
 
@@ -46,6 +46,11 @@ pub fn turbo() -> TurboGradient {
     TurboGradient {}
 }
 
+/// Lookup table approximation of [`turbo`] using `size` colors, faster to evaluate
+pub fn turbo_lut(size: usize) -> LinearGradient {
+    turbo().to_lut(size)
+}
+
 impl Gradient for TurboGradient {
     fn at(&self, t: f32) -> Color {
         let t = t.clamp(0.0, 1.0);
@@ -119,11 +124,11 @@ impl Cubehelix {
         Color::new(r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0), 1.0)
     }
 
-    fn interpolate(&self, other: &Cubehelix, t: f32) -> Cubehelix {
+    fn interpolate(&self, other: &Cubehelix, t: f32, gamma: f32) -> Cubehelix {
         Cubehelix {
             h: self.h + t * (other.h - self.h),
             s: self.s + t * (other.s - self.s),
-            l: self.l + t * (other.l - self.l),
+            l: self.l + t.powf(gamma) * (other.l - self.l),
         }
     }
 }
@@ -134,12 +139,13 @@ impl Cubehelix {
 pub struct CubehelixGradient {
     start: Cubehelix,
     end: Cubehelix,
+    gamma: f32,
 }
 
 impl Gradient for CubehelixGradient {
     fn at(&self, t: f32) -> Color {
         self.start
-            .interpolate(&self.end, t.clamp(0.0, 1.0))
+            .interpolate(&self.end, t.clamp(0.0, 1.0), self.gamma)
             .to_color()
     }
 }
@@ -156,6 +162,39 @@ pub fn cubehelix_default() -> CubehelixGradient {
             s: 0.5,
             l: 1.0,
         },
+        gamma: 1.0,
+    }
+}
+
+/// Cubehelix gradient using the parameters of Dave Green's cubehelix color scheme
+///
+/// * `start` - the start color, `0.0` is blue, `1.0` is red and `2.0` is green
+/// * `rotations` - the number of R → G → B rotations from the start to the end
+/// * `hue` - the saturation, `0.0` is grayscale
+/// * `gamma` - emphasizes the low (`gamma < 1.0`) or the high (`gamma > 1.0`) intensities
+///
+/// `cubehelix(0.5, -1.5, 1.0, 1.0)` is [`cubehelix_default`].
+///
+/// ```
+/// use colorgrad::Gradient;
+///
+/// let g = colorgrad::preset::cubehelix(0.5, -1.5, 1.0, 1.0);
+/// assert_eq!(g.at(0.5).to_rgba8(), colorgrad::preset::cubehelix_default().at(0.5).to_rgba8());
+/// ```
+pub fn cubehelix(start: f32, rotations: f32, hue: f32, gamma: f32) -> CubehelixGradient {
+    let h = start / 3.0 * 360.0 + 240.0;
+    CubehelixGradient {
+        start: Cubehelix {
+            h,
+            s: hue / 2.0,
+            l: 0.0,
+        },
+        end: Cubehelix {
+            h: h + rotations * 360.0,
+            s: hue / 2.0,
+            l: 1.0,
+        },
+        gamma,
     }
 }
 
@@ -171,6 +210,7 @@ pub fn warm() -> CubehelixGradient {
             s: 1.5,
             l: 0.8,
         },
+        gamma: 1.0,
     }
 }
 
@@ -186,6 +226,7 @@ pub fn cool() -> CubehelixGradient {
             s: 1.5,
             l: 0.8,
         },
+        gamma: 1.0,
     }
 }
 
@@ -219,7 +260,7 @@ fn build_preset(html_colors: &[&str]) -> BasisGradient {
         .map(|s| csscolorparser::parse(s).unwrap())
         .collect::<Vec<_>>();
     let pos = linspace(0.0, 1.0, colors.len());
-    BasisGradient::new(&colors, pos, BlendMode::Rgb)
+    BasisGradient::new(&colors, pos, BlendMode::Rgb, HueDirection::Shorter)
 }
 
 macro_rules! preset {
@@ -269,3 +310,72 @@ preset!(&["#ffffd9", "#edf8b1", "#c7e9b4", "#7fcdbb", "#41b6c4", "#1d91c0", "#22
 preset!(&["#ffffe5", "#f7fcb9", "#d9f0a3", "#addd8e", "#78c679", "#41ab5d", "#238443", "#006837", "#004529"]; yl_gn);
 preset!(&["#ffffe5", "#fff7bc", "#fee391", "#fec44f", "#fe9929", "#ec7014", "#cc4c02", "#993404", "#662506"]; yl_or_br);
 preset!(&["#ffffcc", "#ffeda0", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#bd0026", "#800026"]; yl_or_rd);
+
+// Blending
+
+// Preset gradient by the name of its function, e.g. "viridis"
+fn by_name(name: &str) -> Option<Box<dyn Gradient>> {
+    let g: Box<dyn Gradient> = match name {
+        "sinebow" => Box::new(sinebow()),
+        "turbo" => Box::new(turbo()),
+        "cividis" => Box::new(cividis()),
+        "cubehelix_default" => Box::new(cubehelix_default()),
+        "warm" => Box::new(warm()),
+        "cool" => Box::new(cool()),
+        "rainbow" => Box::new(rainbow()),
+        "br_bg" => Box::new(br_bg()),
+        "pr_gn" => Box::new(pr_gn()),
+        "pi_yg" => Box::new(pi_yg()),
+        "pu_or" => Box::new(pu_or()),
+        "rd_bu" => Box::new(rd_bu()),
+        "rd_gy" => Box::new(rd_gy()),
+        "rd_yl_bu" => Box::new(rd_yl_bu()),
+        "rd_yl_gn" => Box::new(rd_yl_gn()),
+        "spectral" => Box::new(spectral()),
+        "blues" => Box::new(blues()),
+        "greens" => Box::new(greens()),
+        "greys" => Box::new(greys()),
+        "oranges" => Box::new(oranges()),
+        "purples" => Box::new(purples()),
+        "reds" => Box::new(reds()),
+        "viridis" => Box::new(viridis()),
+        "inferno" => Box::new(inferno()),
+        "magma" => Box::new(magma()),
+        "plasma" => Box::new(plasma()),
+        "bu_gn" => Box::new(bu_gn()),
+        "bu_pu" => Box::new(bu_pu()),
+        "gn_bu" => Box::new(gn_bu()),
+        "or_rd" => Box::new(or_rd()),
+        "pu_bu_gn" => Box::new(pu_bu_gn()),
+        "pu_bu" => Box::new(pu_bu()),
+        "pu_rd" => Box::new(pu_rd()),
+        "rd_pu" => Box::new(rd_pu()),
+        "yl_gn_bu" => Box::new(yl_gn_bu()),
+        "yl_gn" => Box::new(yl_gn()),
+        "yl_or_br" => Box::new(yl_or_br()),
+        "yl_or_rd" => Box::new(yl_or_rd()),
+        _ => return None,
+    };
+    Some(g)
+}
+
+/// Blend two preset gradients, looked up by the name of their function (e.g. `"viridis"`),
+/// and bake the result into a [`LinearGradient`] of `n` colors (at least 2)
+///
+/// `f` is the weight of `b`, from `0.0` (only `a`) to `1.0` (only `b`). The colors are
+/// blended in [`BlendMode::Oklab`], see [`blend_many`](crate::blend_many). Returns `None`
+/// if a name is unknown.
+///
+/// ```
+/// use colorgrad::Gradient;
+///
+/// let g = colorgrad::preset::blend_presets("viridis", "magma", 0.5, 64).unwrap();
+/// assert_eq!(g.domain(), (0.0, 1.0));
+///
+/// assert!(colorgrad::preset::blend_presets("viridis", "nope", 0.5, 64).is_none());
+/// ```
+pub fn blend_presets(a: &str, b: &str, f: f32, n: usize) -> Option<LinearGradient> {
+    let (a, b) = (by_name(a)?, by_name(b)?);
+    let f = f.clamp(0.0, 1.0);
+    Some(crate::blend_many(&[(&*a, 1.0 - f), (&*b, f)], BlendMode::Oklab).to_lut(n))
+}
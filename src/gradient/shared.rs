@@ -0,0 +1,43 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{BlendMode, Color, Gradient};
+
+/// Reference counted gradient, cloning it is cheap and it can be shared across threads.
+///
+/// Created by [`Gradient::shared`].
+#[derive(Clone)]
+pub struct SharedGradient {
+    gradient: Arc<dyn Gradient + Send + Sync>,
+}
+
+impl SharedGradient {
+    pub(crate) fn new(gradient: Arc<dyn Gradient + Send + Sync>) -> Self {
+        Self { gradient }
+    }
+
+    /// Returns `true` if the two `SharedGradient`s point to the same gradient.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.gradient, &other.gradient)
+    }
+}
+
+impl fmt::Debug for SharedGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedGradient").finish_non_exhaustive()
+    }
+}
+
+impl Gradient for SharedGradient {
+    fn at(&self, t: f32) -> Color {
+        self.gradient.at(t)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+
+    fn mode(&self) -> Option<BlendMode> {
+        self.gradient.mode()
+    }
+}
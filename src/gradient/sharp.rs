@@ -1,4 +1,4 @@
-use crate::{convert_colors, linspace, BlendMode, Color, Gradient};
+use crate::{convert_colors, linspace, to_color, BlendMode, Color, Gradient, HueDirection};
 
 #[cfg_attr(
     feature = "preset",
@@ -13,12 +13,13 @@ let g = colorgrad::preset::rainbow().sharp(11, 0.0);
 pub struct SharpGradient {
     stops: Vec<(f32, [f32; 4])>,
     domain: (f32, f32),
+    mode: BlendMode,
     first_color: Color,
     last_color: Color,
 }
 
 impl SharpGradient {
-    pub(crate) fn new(colors_in: &[Color], domain: (f32, f32), t: f32) -> Self {
+    pub(crate) fn new(colors_in: &[Color], domain: (f32, f32), t: f32, mode: BlendMode) -> Self {
         let n = colors_in.len();
         let mut colors = Vec::with_capacity(n * 2);
 
@@ -49,17 +50,17 @@ impl SharpGradient {
             j += 1;
         }
 
-        let colors = convert_colors(&colors, BlendMode::Rgb);
         let first_color = colors_in[0].clone();
         let last_color = colors_in[n - 1].clone();
 
         Self {
             stops: positions
                 .iter()
-                .zip(colors.iter())
-                .map(|(p, c)| (*p, *c))
+                .copied()
+                .zip(convert_colors(&colors, mode, HueDirection::Shorter))
                 .collect(),
             domain,
+            mode,
             first_color,
             last_color,
         }
@@ -101,21 +102,24 @@ impl Gradient for SharpGradient {
         let (pos_1, col_1) = &self.stops[low];
 
         if i & 1 == 0 {
-            return Color::new(col_0[0], col_0[1], col_0[2], col_0[3]);
+            return to_color(col_0, self.mode);
         }
 
         let t = (t - pos_0) / (pos_1 - pos_0);
-        let [a, b, c, d] = smoothstep(col_0, col_1, t);
-        Color::new(a, b, c, d)
+        to_color(&smoothstep(col_0, col_1, t), self.mode)
     }
 
     fn domain(&self) -> (f32, f32) {
         self.domain
     }
+
+    fn mode(&self) -> Option<BlendMode> {
+        Some(self.mode)
+    }
 }
 
 #[inline]
-fn smoothstep(a: &[f32; 4], b: &[f32; 4], t: f32) -> [f32; 4] {
+pub(crate) fn smoothstep(a: &[f32; 4], b: &[f32; 4], t: f32) -> [f32; 4] {
     [
         (b[0] - a[0]) * (3.0 - t * 2.0) * t * t + a[0],
         (b[1] - a[1]) * (3.0 - t * 2.0) * t * t + a[1],
@@ -0,0 +1,115 @@
+use crate::gradient::linear::search;
+use crate::gradient::sharp::smoothstep;
+use crate::{
+    convert_colors, linspace, nearest_stop, to_color, BlendMode, Color, Gradient, HueDirection,
+};
+
+/// Gradient with smoothstep transitions between evenly spaced colors.
+///
+/// Created by [`Gradient::smoothstepped`]. The colors are blended in the given blend mode,
+/// the transitions ease in and out at every color.
+///
+/// ```
+/// use colorgrad::Gradient;
+///
+/// let g = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#f00", "#00f"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap()
+///     .smoothstepped(2);
+///
+/// assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+/// assert_eq!(g.at(0.5).to_css_hex(), "#800080");
+/// assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SmoothstepGradient {
+    stops: Vec<(f32, [f32; 4])>,
+    domain: (f32, f32),
+    mode: BlendMode,
+    first_color: Color,
+    last_color: Color,
+}
+
+impl SmoothstepGradient {
+    pub(crate) fn new(colors: &[Color], domain: (f32, f32), mode: BlendMode) -> Self {
+        let positions = linspace(domain.0, domain.1, colors.len());
+
+        Self {
+            stops: positions
+                .into_iter()
+                .zip(convert_colors(colors, mode, HueDirection::Shorter))
+                .collect(),
+            domain,
+            mode,
+            first_color: colors[0].clone(),
+            last_color: colors[colors.len() - 1].clone(),
+        }
+    }
+
+    /// Get the color of the stop nearest to `t`, without interpolation.
+    pub fn snap_to_stop(&self, t: f32) -> Color {
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        self.stop_color(nearest_stop(self.stops.len(), t, |i| self.stops[i].0))
+    }
+
+    /// Get every segment between two adjacent colors as `(pos_0, color_0, pos_1, color_1)`,
+    /// useful for segment based rendering.
+    pub fn segments(&self) -> Vec<(f32, Color, f32, Color)> {
+        (1..self.stops.len())
+            .map(|i| {
+                (
+                    self.stops[i - 1].0,
+                    self.stop_color(i - 1),
+                    self.stops[i].0,
+                    self.stop_color(i),
+                )
+            })
+            .collect()
+    }
+
+    fn stop_color(&self, i: usize) -> Color {
+        if i == 0 {
+            return self.first_color.clone();
+        }
+
+        if i == self.stops.len() - 1 {
+            return self.last_color.clone();
+        }
+
+        to_color(&self.stops[i].1, self.mode)
+    }
+}
+
+impl Gradient for SmoothstepGradient {
+    fn at(&self, t: f32) -> Color {
+        if t <= self.domain.0 {
+            return self.first_color.clone();
+        }
+
+        if t >= self.domain.1 {
+            return self.last_color.clone();
+        }
+
+        if t.is_nan() {
+            return Color::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        let low = search(&self.stops, t);
+        let (pos_0, col_0) = &self.stops[low - 1];
+        let (pos_1, col_1) = &self.stops[low];
+        let t = (t - pos_0) / (pos_1 - pos_0);
+        to_color(&smoothstep(col_0, col_1, t), self.mode)
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        self.domain
+    }
+
+    fn mode(&self) -> Option<BlendMode> {
+        Some(self.mode)
+    }
+}
@@ -14,7 +14,7 @@ let g = colorgrad::preset::rainbow();
 
 assert_eq!(g.domain(), (0.0, 1.0)); // all preset gradients are in the domain [0..1]
 assert_eq!(g.at(0.5).to_rgba8(), [175, 240, 91, 255]);
-assert_eq!(g.at(0.5).to_hex_string(), "#aff05b");
+assert_eq!(g.at(0.5).to_css_hex(), "#aff05b");
 ```"##
 )]
 //!
@@ -31,8 +31,8 @@ assert_eq!(g.at(0.5).to_hex_string(), "#aff05b");
 //!     .build::<colorgrad::LinearGradient>()?;
 //!
 //! assert_eq!(g.at(0.0).to_rgba8(), [255, 0, 0, 255]);
-//! assert_eq!(g.at(0.0).to_hex_string(), "#ff0000");
-//! assert_eq!(g.at(1.0).to_hex_string(), "#00ff00");
+//! assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+//! assert_eq!(g.at(1.0).to_css_hex(), "#00ff00");
 //! # Ok(())
 //! # }
 //! ```
@@ -44,7 +44,7 @@ assert_eq!(g.at(0.5).to_hex_string(), "#aff05b");
 #![cfg_attr(
     feature = "named-colors",
     doc = r##"
-```
+```no_run
 use colorgrad::Gradient;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -133,15 +133,27 @@ Example output:
 pub use csscolorparser::{Color, ParseColorError};
 
 mod builder;
-pub use builder::{GradientBuilder, GradientBuilderError};
+pub use builder::{GradientBuilder, GradientBuilderError, GradientBuilderWarning};
 
 mod css_gradient;
 
 mod gradient;
+pub use gradient::alpha_scale::AlphaScaleGradient;
+pub use gradient::alpha_stops::AlphaStopsGradient;
 pub use gradient::basis::BasisGradient;
+pub use gradient::blend_many::BlendManyGradient;
+pub use gradient::bounded::BoundedGradient;
 pub use gradient::catmull_rom::CatmullRomGradient;
-pub use gradient::linear::LinearGradient;
+pub use gradient::channel::ChannelGradient;
+pub use gradient::clamp_lightness::ClampLightnessGradient;
+pub use gradient::effects::GradientEffects;
+pub use gradient::gamut_map::GamutMapGradient;
+pub use gradient::hue_shift::HueShiftGradient;
+pub use gradient::linear::{LinearGradient, ScanlineSampler};
+pub use gradient::nan_color::NanColorGradient;
+pub use gradient::shared::SharedGradient;
 pub use gradient::sharp::SharpGradient;
+pub use gradient::smoothstep::SmoothstepGradient;
 
 #[cfg(feature = "preset")]
 pub use gradient::preset;
@@ -150,19 +162,120 @@ pub use gradient::preset;
 pub use gradient::gimp::{GimpGradient, ParseGgrError};
 
 /// Color blending mode
+///
+/// `Lab` and `Lch` only exist when the `lab` feature is enabled, using them without the
+/// feature is a compile error.
+#[cfg_attr(
+    not(feature = "lab"),
+    doc = r##"
+```compile_fail
+let mode = colorgrad::BlendMode::Lab;
+```"##
+)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum BlendMode {
     Rgb,
     LinearRgb,
     Oklab,
+    /// HSL, the hue is interpolated along the shortest arc by default, see
+    /// [`GradientBuilder::hue_direction`]
+    Hsl,
     #[cfg(feature = "lab")]
     Lab,
+    /// Cylindrical Lab (L\*C\*h\*), the hue is interpolated along the shortest arc by default,
+    /// see [`GradientBuilder::hue_direction`]
+    #[cfg(feature = "lab")]
+    Lch,
+}
+
+/// Interpolation of a single color channel, see [`GradientBuilder::channel_modes`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ChannelMode {
+    /// Interpolate the channel value linearly
+    Linear,
+    /// Interpolate the channel as an angle in degrees, along the shortest arc by default, see
+    /// [`GradientBuilder::hue_direction`]
+    Circular,
+}
+
+/// Direction of hue interpolation, see [`GradientBuilder::hue_direction`]
+///
+/// Like the CSS
+/// [`hue-interpolation-method`](https://developer.mozilla.org/en-US/docs/Web/CSS/hue-interpolation-method).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum HueDirection {
+    /// Along the shortest arc, the default
+    Shorter,
+    /// Along the longest arc
+    Longer,
+    /// Counterclockwise, the hue is always increasing
+    Increasing,
+    /// Clockwise, the hue is always decreasing
+    Decreasing,
+}
+
+/// Color channel, see [`Gradient::channel`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Channel {
+    R,
+    G,
+    B,
+    A,
+    /// Relative luminance, computed from the linear RGB values
+    Luminance,
+}
+
+// Just noticeable difference, Euclidean distance in Oklab color space
+pub(crate) const JND: f32 = 0.02;
+
+/// Default tolerance of [`Gradient::is_cyclic`], used by [`Gradient::stats`]
+pub const CYCLE_TOLERANCE: f32 = 0.01;
+
+/// Summary of a gradient's perceptual properties, see [`Gradient::stats`]
+///
+/// Lightness and chroma are in Oklch color space.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GradientStats {
+    /// Minimum and maximum lightness
+    pub lightness_range: (f32, f32),
+    /// Minimum and maximum chroma
+    pub chroma_range: (f32, f32),
+    /// The first and the last colors are the same, see [`Gradient::is_cyclic`]
+    pub cyclic: bool,
+    /// The lightness is either increasing or decreasing
+    pub monotone_lightness: bool,
+    /// Position and size of the largest step between adjacent samples, see
+    /// [`Gradient::max_step`]
+    pub max_step: (f32, f32),
 }
 
 pub trait Gradient: CloneGradient {
     /// Get color at certain position
     fn at(&self, t: f32) -> Color;
 
+    /// Get color at certain position in `[0, 1]`, mapped onto the gradient's domain
+    fn at_unit(&self, t: f32) -> Color {
+        let (dmin, dmax) = self.domain();
+        self.at(dmin + t * (dmax - dmin))
+    }
+
+    /// Get color at a signed position `s` in `[-1, 1]`, for diverging data centered on zero
+    ///
+    /// `-1` maps to the domain min, `0` to the domain center and `1` to the domain max.
+    fn at_signed(&self, s: f32) -> Color {
+        self.at_unit((s + 1.0) / 2.0)
+    }
+
+    /// Get the color at the center of the bucket `index` when the domain is divided in
+    /// `levels` buckets
+    ///
+    /// `index` is clamped to `levels - 1`.
+    fn at_index(&self, index: usize, levels: usize) -> Color {
+        let levels = levels.max(1);
+        let index = index.min(levels - 1);
+        self.at_unit((index as f32 + 0.5) / levels as f32)
+    }
+
     /// Get color at certain position
     fn repeat_at(&self, t: f32) -> Color {
         let (dmin, dmax) = self.domain();
@@ -182,6 +295,14 @@ pub trait Gradient: CloneGradient {
         (0.0, 1.0)
     }
 
+    /// Get the blend mode the gradient was built with
+    ///
+    /// `None` for gradients which are not blending colors using a [`BlendMode`], like the
+    /// analytic preset gradients (e.g. `preset::rainbow()`).
+    fn mode(&self) -> Option<BlendMode> {
+        None
+    }
+
     /// Get n colors evenly spaced across gradient
     fn colors(&self, n: usize) -> Vec<Color> {
         let (dmin, dmax) = self.domain();
@@ -192,6 +313,844 @@ pub trait Gradient: CloneGradient {
             .collect()
     }
 
+    /// Fill `buf` with colors evenly spaced across gradient, anti-aliased
+    ///
+    /// Each color is the average of a few samples, computed in linear light, spread over
+    /// `footprint` (in domain units) centered on its position. A footprint of about
+    /// `(dmax - dmin) / buf.len()` smooths the hard edges of sharp gradients when rendering
+    /// images. A `footprint` of zero (or not finite) gives the same colors as
+    /// [`colors`](Gradient::colors).
+    fn sample_aa_into(&self, buf: &mut [Color], footprint: f32) {
+        const SAMPLES: usize = 4;
+
+        let (dmin, dmax) = self.domain();
+        let footprint = if footprint.is_finite() {
+            footprint.abs()
+        } else {
+            0.0
+        };
+
+        let positions = linspace(dmin, dmax, buf.len());
+
+        for (c, t) in buf.iter_mut().zip(positions) {
+            if footprint == 0.0 {
+                *c = self.at(t).clamp();
+                continue;
+            }
+            let mut sum = [0.0; 4];
+            for k in 0..SAMPLES {
+                let offset = ((k as f32 + 0.5) / SAMPLES as f32 - 0.5) * footprint;
+                let s = self.at(t + offset).clamp().to_linear_rgba();
+                for (v, s) in sum.iter_mut().zip(s) {
+                    *v += s;
+                }
+            }
+            let [r, g, b, a] = sum.map(|v| v / SAMPLES as f32);
+            *c = Color::from_linear_rgba(r, g, b, a);
+        }
+    }
+
+    /// Get n colors evenly spaced inside the gradient, excluding the domain min and max
+    ///
+    /// The colors are sampled at `(i + 1) / (n + 1)` across the domain.
+    fn interior_colors(&self, n: usize) -> Vec<Color> {
+        let (dmin, dmax) = self.domain();
+
+        (1..=n)
+            .map(|i| {
+                self.at(dmin + i as f32 / (n + 1) as f32 * (dmax - dmin))
+                    .clamp()
+            })
+            .collect()
+    }
+
+    /// Get only as many colors as needed to represent the gradient, for compact legends
+    ///
+    /// The domain is subdivided adaptively until, inside every interval, the gradient
+    /// deviates from the RGB interpolation of the interval's end colors by at most
+    /// `threshold` (Euclidean distance in Oklab color space). Returns the colors at the
+    /// intervals bounds, which are not evenly spaced: few colors for smooth gradients and
+    /// more colors around the sharp transitions.
+    fn representative_colors(&self, threshold: f32) -> Vec<Color> {
+        const MAX_DEPTH: u32 = 12;
+
+        let (dmin, dmax) = self.domain();
+        let color = |t: f32| self.at(t).clamp();
+        let dist = |a: &Color, b: &Color| oklab_distance(&a.to_oklaba(), &b.to_oklaba());
+
+        let mut colors = vec![color(dmin)];
+        let mut stack = vec![(dmin, dmax, colors[0].clone(), color(dmax), 0)];
+
+        while let Some((a, b, ca, cb, depth)) = stack.pop() {
+            let fits = depth >= MAX_DEPTH
+                || [0.25, 0.5, 0.75].iter().all(|&f| {
+                    dist(&color(a + f * (b - a)), &ca.interpolate_rgb(&cb, f)) <= threshold
+                });
+
+            if fits {
+                colors.push(cb);
+            } else {
+                let mid = (a + b) / 2.0;
+                let cm = color(mid);
+                // the left interval is processed first
+                stack.push((mid, b, cm.clone(), cb, depth + 1));
+                stack.push((a, mid, ca, cm, depth + 1));
+            }
+        }
+
+        colors
+    }
+
+    /// Get n colors evenly spaced across gradient as two RGBA8 buffers, the first one
+    /// sRGB encoded and the second one linear-light (decoded)
+    fn to_dual_texture(&self, n: usize) -> (Vec<u8>, Vec<u8>) {
+        let mut srgb = Vec::with_capacity(n * 4);
+        let mut linear = Vec::with_capacity(n * 4);
+
+        for c in self.colors(n) {
+            srgb.extend_from_slice(&c.to_rgba8());
+            linear.extend_from_slice(&c.to_linear_rgba_u8());
+        }
+
+        (srgb, linear)
+    }
+
+    /// Get a preview row of RGBA8 pixels fitting in `max_bytes`
+    ///
+    /// The width is the largest one such that `width * 4 <= max_bytes`. Returns the width
+    /// and the pixels, colors evenly spaced across gradient.
+    fn preview_rgba8(&self, max_bytes: usize) -> (u32, Vec<u8>) {
+        let width = (max_bytes / 4).min(u32::MAX as usize);
+        let pixels = self
+            .colors(width)
+            .iter()
+            .flat_map(|c| c.to_rgba8())
+            .collect();
+        (width as u32, pixels)
+    }
+
+    /// Get color at certain position as Display P3 RGBA, for wide-gamut renderers
+    ///
+    /// The sRGB color is converted to linear RGB, then to the Display P3 primaries, then
+    /// encoded using the sRGB transfer function (which Display P3 shares).
+    fn at_p3(&self, t: f32) -> [f32; 4] {
+        let [r, g, b, a] = self.at(t).to_linear_rgba();
+        let p3 = Color::from_linear_rgba(
+            0.822_462_1 * r + 0.177_538 * g,
+            0.033_194_2 * r + 0.966_805_8 * g,
+            0.017_082_7 * r + 0.072_397_4 * g + 0.910_519_9 * b,
+            a,
+        );
+        p3.to_array()
+    }
+
+    /// Get color at certain position as half-float RGBA
+    ///
+    /// Requires the `half` feature.
+    #[cfg(feature = "half")]
+    fn at_rgba_f16(&self, t: f32) -> [half::f16; 4] {
+        self.at(t).to_array().map(half::f16::from_f32)
+    }
+
+    /// Get n colors evenly spaced across gradient as a half-float RGBA buffer
+    ///
+    /// Requires the `half` feature.
+    #[cfg(feature = "half")]
+    fn to_rgba_f16_buffer(&self, n: usize) -> Vec<half::f16> {
+        self.colors(n)
+            .iter()
+            .flat_map(|c| c.to_array())
+            .map(half::f16::from_f32)
+            .collect()
+    }
+
+    /// Get 1D `.cube` LUT file content using `size` colors evenly spaced across gradient
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_cube_lut_1d(2),
+    ///     "LUT_1D_SIZE 2\n1.000000 0.000000 0.000000\n0.000000 0.000000 1.000000\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_cube_lut_1d(&self, size: usize) -> String {
+        let mut s = format!("LUT_1D_SIZE {}\n", size);
+
+        for c in self.colors(size) {
+            s.push_str(&format!("{:.6} {:.6} {:.6}\n", c.r, c.g, c.b));
+        }
+
+        s
+    }
+
+    /// Get [Lottie](https://lottiefiles.github.io/lottie-docs/) gradient JSON object using
+    /// n colors evenly spaced across gradient
+    ///
+    /// The returned object is the value of the `g` property of a gradient fill or stroke:
+    /// the number of stops `p` and the flat `[offset, r, g, b, ...]` color stops followed by
+    /// the `[offset, alpha, ...]` opacity stops.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_lottie_ramp(2),
+    ///     r#"{"p":2,"k":{"a":0,"k":[0,1,0,0,1,0,0,1,0,1,1,1]}}"#
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_lottie_ramp(&self, n: usize) -> String {
+        let positions = linspace(0.0, 1.0, n);
+        let colors = self.colors(n);
+        let mut values = Vec::with_capacity(n * 6);
+
+        for (t, c) in positions.iter().zip(&colors) {
+            values.push(t.to_string());
+            values.push(c.r.to_string());
+            values.push(c.g.to_string());
+            values.push(c.b.to_string());
+        }
+
+        for (t, c) in positions.iter().zip(&colors) {
+            values.push(t.to_string());
+            values.push(c.a.to_string());
+        }
+
+        format!(r#"{{"p":{},"k":{{"a":0,"k":[{}]}}}}"#, n, values.join(","))
+    }
+
+    /// Render the gradient repeated `repeats` times across the image width
+    ///
+    /// Requires the `image` feature.
+    #[cfg(feature = "image")]
+    fn to_repeating_image(&self, width: u32, height: u32, repeats: f32) -> image::RgbaImage {
+        let (dmin, dmax) = self.domain();
+        let row = (0..width)
+            .map(|x| {
+                let t = x as f32 / width as f32 * repeats;
+                self.repeat_at(dmin + t * (dmax - dmin)).to_rgba8()
+            })
+            .collect::<Vec<_>>();
+
+        image::RgbaImage::from_fn(width, height, |x, _| image::Rgba(row[x as usize]))
+    }
+
+    /// Get a function mapping data values in `[data_min, data_max]` across the gradient
+    /// domain to `image::Rgba<u8>`, for the per-value color closures of drawing crates like
+    /// `imageproc`
+    ///
+    /// Values outside the data range get the colors of the domain ends. If `data_min`
+    /// equals `data_max` every value gets the color at the domain min.
+    ///
+    /// Requires the `image` feature.
+    #[cfg(feature = "image")]
+    fn as_value_colorizer(
+        &self,
+        data_min: f32,
+        data_max: f32,
+    ) -> impl Fn(f32) -> image::Rgba<u8> + '_
+    where
+        Self: Sized,
+    {
+        let (dmin, dmax) = self.domain();
+        let range = data_max - data_min;
+        move |v| {
+            let u = if range == 0.0 {
+                0.0
+            } else {
+                (v - data_min) / range
+            };
+            image::Rgba(self.at(dmin + u * (dmax - dmin)).to_rgba8())
+        }
+    }
+
+    /// Render a classified legend strip of `bands` discrete color bands, separated by 1
+    /// pixel wide black lines
+    ///
+    /// The color of each band is the color at the center of its bucket, see
+    /// [`at_index`](Gradient::at_index).
+    ///
+    /// Requires the `image` feature.
+    #[cfg(feature = "image")]
+    fn to_legend_image(&self, width: u32, height: u32, bands: usize) -> image::RgbaImage {
+        let bands = bands.max(1);
+        let colors = (0..bands)
+            .map(|i| self.at_index(i, bands).to_rgba8())
+            .collect::<Vec<_>>();
+        let band = |x: u32| (x as usize * bands / width as usize).min(bands - 1);
+
+        image::RgbaImage::from_fn(width, height, |x, _| {
+            if x > 0 && band(x) != band(x - 1) {
+                image::Rgba([0, 0, 0, 255])
+            } else {
+                image::Rgba(colors[band(x)])
+            }
+        })
+    }
+
+    /// Render a 2D image, the color of the pixel at `(x, y)` is the gradient color at the
+    /// position `f(x, y)`
+    ///
+    /// With the `rayon` feature, the rows are rendered in parallel.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// // horizontal gradient repeated down the rows
+    /// let img = g.render_image(100, 50, |x, _| x as f32 / 99.0);
+    /// assert_eq!(img.get_pixel(99, 49).0, [0, 0, 255, 255]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "image")]
+    fn render_image<F>(&self, width: u32, height: u32, f: F) -> image::RgbaImage
+    where
+        Self: Sized + Sync,
+        F: Fn(u32, u32) -> f32 + Sync,
+    {
+        let mut img = image::RgbaImage::new(width, height);
+        if width == 0 || height == 0 {
+            return img;
+        }
+
+        let render_row = |(y, row): (usize, &mut [u8])| {
+            for (x, px) in row.chunks_exact_mut(4).enumerate() {
+                px.copy_from_slice(&self.at(f(x as u32, y as u32)).to_rgba8());
+            }
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            img.par_chunks_exact_mut(width as usize * 4)
+                .enumerate()
+                .for_each(render_row);
+        }
+        #[cfg(not(feature = "rayon"))]
+        img.chunks_exact_mut(width as usize * 4)
+            .enumerate()
+            .for_each(render_row);
+
+        img
+    }
+
+    /// Write the gradient as an indexed-color PNG strip of `width` pixels and 1 pixel height
+    ///
+    /// The palette has `palette_size` (1 to 256) colors evenly spaced across gradient, each
+    /// pixel uses the palette color nearest to its position. Much smaller than a truecolor
+    /// image for smooth gradients.
+    ///
+    /// Requires the `png` feature.
+    #[cfg(feature = "png")]
+    fn write_indexed_png<W: std::io::Write>(
+        &self,
+        w: W,
+        width: u32,
+        palette_size: usize,
+    ) -> Result<(), png::EncodingError>
+    where
+        Self: Sized,
+    {
+        let palette_size = palette_size.clamp(1, 256);
+        let colors = self.colors(palette_size);
+
+        let palette = colors
+            .iter()
+            .flat_map(|c| {
+                let [r, g, b, _] = c.to_rgba8();
+                [r, g, b]
+            })
+            .collect::<Vec<_>>();
+        let trns = colors.iter().map(|c| c.to_rgba8()[3]).collect::<Vec<_>>();
+
+        let last = (palette_size - 1) as f32;
+        let data = (0..width)
+            .map(|x| {
+                let t = x as f32 / (width.max(2) - 1) as f32;
+                (t * last).round() as u8
+            })
+            .collect::<Vec<_>>();
+
+        let mut encoder = png::Encoder::new(w, width, 1);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(palette);
+        if trns.iter().any(|&a| a < 255) {
+            encoder.set_trns(trns);
+        }
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()
+    }
+
+    /// Render the gradient as a row of `width` RGBA8 pixels, each pixel being the average
+    /// of `supersample` samples computed in linear light
+    ///
+    /// Averaging in linear light avoids the darkening of averaging sRGB values.
+    ///
+    /// Requires the `image` feature.
+    #[cfg(feature = "image")]
+    fn to_image_linear_avg(&self, width: u32, supersample: u32) -> Vec<u8> {
+        let (dmin, dmax) = self.domain();
+        let n = supersample.max(1);
+        let mut row = Vec::with_capacity(width as usize * 4);
+
+        for x in 0..width {
+            let mut sum = [0.0; 4];
+            for k in 0..n {
+                let t = (x as f32 + (k as f32 + 0.5) / n as f32) / width as f32;
+                let c = self.at(dmin + t * (dmax - dmin)).clamp().to_linear_rgba();
+                for (v, c) in sum.iter_mut().zip(c) {
+                    *v += c;
+                }
+            }
+            let [r, g, b, a] = sum.map(|v| v / n as f32);
+            row.extend_from_slice(&Color::from_linear_rgba(r, g, b, a).to_rgba8());
+        }
+
+        row
+    }
+
+    /// Find the largest color change between adjacent samples
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions. Returns the position
+    /// in the middle of the two adjacent samples with the largest difference, and that
+    /// difference (Euclidean distance in Oklab color space). Useful to find where a
+    /// gradient is most prone to aliasing.
+    fn max_step(&self, samples: usize) -> (f32, f32) {
+        let positions = linspace(self.domain().0, self.domain().1, samples);
+        let colors = positions
+            .iter()
+            .map(|&t| self.at(t).to_oklaba())
+            .collect::<Vec<_>>();
+
+        let mut res = (self.domain().0, 0.0);
+
+        for (i, c) in colors.windows(2).enumerate() {
+            let d = oklab_distance(&c[0], &c[1]);
+            if d > res.1 {
+                res = ((positions[i] + positions[i + 1]) / 2.0, d);
+            }
+        }
+
+        res
+    }
+
+    /// Estimate the number of perceptually distinct colors of the gradient, to choose the
+    /// number of classes of a classified map
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions, a new distinct color
+    /// is counted every time the color is noticeably different (Euclidean distance in Oklab
+    /// color space above `0.02`) from the last distinct color.
+    fn jnd_color_count(&self, samples: usize) -> usize {
+        let colors = self
+            .colors(samples)
+            .iter()
+            .map(|c| c.to_oklaba())
+            .collect::<Vec<_>>();
+
+        let mut last = match colors.first() {
+            Some(c) => c,
+            None => return 0,
+        };
+        let mut count = 1;
+
+        for c in &colors[1..] {
+            if oklab_distance(c, last) > JND {
+                count += 1;
+                last = c;
+            }
+        }
+
+        count
+    }
+
+    /// Count the distinct RGBA8 colors of `width` colors evenly spaced across gradient
+    ///
+    /// Useful before exporting to 8-bit formats, e.g. to know if a GIF will hit the 256
+    /// colors limit.
+    fn distinct_rgba8_count(&self, width: usize) -> usize {
+        self.colors(width)
+            .iter()
+            .map(|c| c.to_rgba8())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    /// Find the position in `[t0, t1]` whose color is perceptually halfway between the
+    /// colors at `t0` and `t1`
+    ///
+    /// The range is sampled at `samples` evenly spaced positions, returns the position
+    /// minimizing the difference of its Euclidean distances in Oklab color space to the
+    /// two end colors. Useful to place a middle swatch which looks centered.
+    fn perceptual_midpoint(&self, t0: f32, t1: f32, samples: usize) -> f32 {
+        let c0 = self.at(t0).to_oklaba();
+        let c1 = self.at(t1).to_oklaba();
+
+        let mut res = ((t0 + t1) / 2.0, f32::INFINITY);
+        for t in linspace(t0, t1, samples.max(2)) {
+            let c = self.at(t).to_oklaba();
+            let diff = (oklab_distance(&c, &c0) - oklab_distance(&c, &c1)).abs();
+            if diff < res.1 {
+                res = (t, diff);
+            }
+        }
+
+        res.0
+    }
+
+    /// Find the first position where a channel crosses `value`, for contour-like effects
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions (at least 2) to find
+    /// the first pair of adjacent samples on both sides of `value`, the position is then
+    /// refined by bisection. Returns `None` if the channel never crosses `value`.
+    fn first_crossing(&self, channel: Channel, value: f32, samples: usize) -> Option<f32> {
+        let (dmin, dmax) = self.domain();
+        let positions = linspace(dmin, dmax, samples.max(2));
+        let f = |t: f32| channel_value(&self.at(t), channel) - value;
+
+        let mut prev = (positions[0], f(positions[0]));
+        if prev.1 == 0.0 {
+            return Some(prev.0);
+        }
+
+        for &t in &positions[1..] {
+            let v = f(t);
+            if v == 0.0 {
+                return Some(t);
+            }
+            if (prev.1 < 0.0) != (v < 0.0) {
+                let (mut lo, mut hi) = (prev.0, t);
+                for _ in 0..32 {
+                    let mid = (lo + hi) / 2.0;
+                    if (f(mid) < 0.0) == (prev.1 < 0.0) {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                return Some((lo + hi) / 2.0);
+            }
+            prev = (t, v);
+        }
+
+        None
+    }
+
+    /// Find, for each of the `targets` colors, the position where the gradient color is
+    /// nearest to it, to build a legend keyed to given colors
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions (at least 2), the
+    /// nearest color is the one with the smallest Euclidean distance in Oklab color space.
+    /// The alpha is ignored.
+    fn positions_for(&self, targets: &[Color], samples: usize) -> Vec<f32> {
+        let (dmin, dmax) = self.domain();
+        let samples = linspace(dmin, dmax, samples.max(2))
+            .into_iter()
+            .map(|t| (t, self.at(t).to_oklaba()))
+            .collect::<Vec<_>>();
+
+        targets
+            .iter()
+            .map(|target| {
+                let c = target.to_oklaba();
+                samples
+                    .iter()
+                    .min_by(|a, b| oklab_distance(&a.1, &c).total_cmp(&oklab_distance(&b.1, &c)))
+                    .map(|s| s.0)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    /// Get the mean squared error between this gradient and `target`, per channel of the
+    /// color space of `mode`, for colormap fitting
+    ///
+    /// Both gradients are sampled at `samples` positions evenly spaced across their
+    /// domain, the red, green, blue (or the channels of `mode`) and alpha channels are
+    /// compared. With the hue based modes, [`BlendMode::Hsl`] and `BlendMode::Lch`, the hue
+    /// difference (in degrees) is taken along the shortest arc.
+    fn mse_against(&self, target: &dyn Gradient, mode: BlendMode, samples: usize) -> f32 {
+        let (dmin, dmax) = self.domain();
+        let (tmin, tmax) = target.domain();
+        let samples = samples.max(1);
+
+        let mut sum = 0.0;
+        for t in linspace(0.0, 1.0, samples) {
+            let a = convert_color(&self.at(dmin + t * (dmax - dmin)), mode);
+            let b = convert_color(&target.at(tmin + t * (tmax - tmin)), mode);
+            let mut d = [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+            if let Some(k) = hue_channel(mode) {
+                d[k] = (d[k] + 180.0).rem_euclid(360.0) - 180.0;
+            }
+            sum += d.iter().map(|v| v * v).sum::<f32>();
+        }
+
+        sum / (samples * 4) as f32
+    }
+
+    /// Find the two positions whose colors have the highest WCAG contrast ratio, to choose
+    /// readable legend background and foreground colors
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions (at least 2). Returns
+    /// the two positions, in increasing order, and their contrast ratio (from 1 to 21).
+    /// The alpha is ignored.
+    fn max_contrast_pair(&self, samples: usize) -> (f32, f32, f32) {
+        let (dmin, dmax) = self.domain();
+        let positions = linspace(dmin, dmax, samples.max(2));
+
+        let mut darkest = (dmin, f32::INFINITY);
+        let mut lightest = (dmin, f32::NEG_INFINITY);
+
+        for &t in &positions {
+            let lum = relative_luminance(&self.at(t).clamp());
+            if lum < darkest.1 {
+                darkest = (t, lum);
+            }
+            if lum > lightest.1 {
+                lightest = (t, lum);
+            }
+        }
+
+        (
+            darkest.0.min(lightest.0),
+            darkest.0.max(lightest.0),
+            (lightest.1 + 0.05) / (darkest.1 + 0.05),
+        )
+    }
+
+    /// Estimate the smallest domain distance over which a noticeable color change occurs
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions, a change is
+    /// noticeable when the Euclidean distance in Oklab color space is above the just
+    /// noticeable difference (`0.02`). Returns the domain width if there is no noticeable
+    /// change. Useful to choose a sampling step which avoids visible banding.
+    fn min_feature_width(&self, samples: usize) -> f32 {
+        let (dmin, dmax) = self.domain();
+        let positions = linspace(dmin, dmax, samples);
+        let colors = positions
+            .iter()
+            .map(|&t| self.at(t).to_oklaba())
+            .collect::<Vec<_>>();
+
+        let mut res = dmax - dmin;
+
+        // Length of the path through the samples up to each sample, the distance between
+        // two samples is at most the path length between them
+        let mut path = Vec::with_capacity(colors.len());
+        let mut len = 0.0;
+        for (i, c) in colors.iter().enumerate() {
+            if i > 0 {
+                len += oklab_distance(&colors[i - 1], c);
+            }
+            path.push(len);
+        }
+
+        if len <= JND {
+            return res;
+        }
+
+        // For each sample, search backward for the nearest noticeably different sample. The
+        // samples within `JND - d` along the path from a sample at distance `d` are not
+        // noticeably different either, they are skipped.
+        for j in 1..colors.len() {
+            let mut i = j - 1;
+            loop {
+                let width = positions[j] - positions[i];
+                if width >= res {
+                    break;
+                }
+                let d = oklab_distance(&colors[i], &colors[j]);
+                if d > JND {
+                    res = width;
+                    break;
+                }
+                let bound = path[i] - (JND - d);
+                match path[..i].partition_point(|&p| p < bound) {
+                    0 => break,
+                    n => i = n - 1,
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Get the positions, among `samples` evenly spaced positions, where the color is out
+    /// of the sRGB gamut
+    ///
+    /// A color is out of gamut when any RGB channel falls outside `[0, 1]` (with a small
+    /// tolerance for rounding errors). Gradients don't clamp their colors, so this detects
+    /// where e.g. an Oklab spline interpolation overshoots.
+    fn out_of_gamut_positions(&self, samples: usize) -> Vec<f32> {
+        const EPSILON: f32 = 1e-4;
+
+        let (dmin, dmax) = self.domain();
+        linspace(dmin, dmax, samples)
+            .into_iter()
+            .filter(|&t| {
+                let c = self.at(t);
+                [c.r, c.g, c.b]
+                    .iter()
+                    .any(|v| *v < -EPSILON || *v > 1.0 + EPSILON)
+            })
+            .collect()
+    }
+
+    /// Get the perceptual profile of the gradient as `[L, C, h]` in Oklch color space,
+    /// sampled at `n` evenly spaced positions
+    ///
+    /// The hue is in degrees, in the range `[0, 360)`. Useful to plot the lightness,
+    /// chroma and hue of a colormap for visual QA.
+    fn lch_profile(&self, n: usize) -> Vec<[f32; 3]> {
+        self.colors(n)
+            .iter()
+            .map(|c| {
+                let [l, c, h, _] = c.to_oklcha();
+                [l, c, h.to_degrees().rem_euclid(360.0)]
+            })
+            .collect()
+    }
+
+    /// Check if the first and the last colors are the same, i.e. the gradient can be
+    /// repeated without a visible seam
+    ///
+    /// The colors are the same if their Euclidean distance in Oklab color space is below
+    /// `tolerance`. [`CYCLE_TOLERANCE`] is the default, a smaller tolerance may be needed
+    /// when rendering at high bit depth.
+    fn is_cyclic(&self, tolerance: f32) -> bool {
+        let (dmin, dmax) = self.domain();
+        oklab_distance(&self.at(dmin).to_oklaba(), &self.at(dmax).to_oklaba()) < tolerance
+    }
+
+    /// Get a summary of the gradient's perceptual properties, computed from `samples`
+    /// colors evenly spaced across gradient
+    ///
+    /// Useful for colormap QA, e.g. lightness should be monotone for sequential data.
+    fn stats(&self, samples: usize) -> GradientStats {
+        let profile = self.lch_profile(samples);
+        let range = |i: usize| {
+            profile
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+                    (min.min(v[i]), max.max(v[i]))
+                })
+        };
+        let lightness = profile.iter().map(|v| v[0]).collect::<Vec<_>>();
+
+        GradientStats {
+            lightness_range: range(0),
+            chroma_range: range(1),
+            cyclic: self.is_cyclic(CYCLE_TOLERANCE),
+            monotone_lightness: is_monotone(&lightness),
+            max_step: self.max_step(samples),
+        }
+    }
+
+    /// Get a lookup table approximation of the gradient, a [`LinearGradient`] of `size`
+    /// colors evenly spaced across the domain
+    ///
+    /// Useful for gradients which are expensive to evaluate, like the analytic presets
+    /// (e.g. `preset::turbo()`), in hot loops. `size` is at least 2.
+    fn to_lut(&self, size: usize) -> LinearGradient {
+        let (dmin, dmax) = self.domain();
+        let size = size.max(2);
+        LinearGradient::new(
+            &self.colors(size),
+            &linspace(dmin, dmax, size),
+            &[],
+            BlendMode::Rgb,
+            None,
+            HueDirection::Shorter,
+        )
+    }
+
+    /// Get a [`GradientBuilder`] populated with `n` colors (at least 2) evenly spaced across
+    /// the domain, at their positions
+    ///
+    /// Useful to keep editing any gradient, like the presets, using the builder API.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::{BlendMode, Gradient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .domain(&[0.0, 100.0])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// let g2 = g
+    ///     .to_builder(5)
+    ///     .mode(BlendMode::Oklab)
+    ///     .build::<colorgrad::CatmullRomGradient>()?;
+    ///
+    /// assert_eq!(g2.domain(), (0.0, 100.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_builder(&self, n: usize) -> GradientBuilder {
+        let (dmin, dmax) = self.domain();
+        let n = n.max(2);
+        let mut gb = GradientBuilder::new();
+        gb.colors(&self.colors(n)).domain(&linspace(dmin, dmax, n));
+        gb
+    }
+
+    /// Get a lookup table of `n` colors (at least 2) evenly spaced across the domain,
+    /// converted to linear RGB, for rendering into linear framebuffers
+    ///
+    /// The red, green and blue of the returned gradient's colors are linear-light values,
+    /// not sRGB encoded, interpolating them in [`BlendMode::Rgb`] keeps the interpolation
+    /// in linear light. Its colors must not be displayed as sRGB colors, they are meant to
+    /// be written to a linear framebuffer or texture (e.g. `Rgba16Float`, or a `*Srgb`
+    /// format which encodes on write).
+    fn to_linear_baked(&self, n: usize) -> LinearGradient {
+        let (dmin, dmax) = self.domain();
+        let n = n.max(2);
+        let colors = self
+            .colors(n)
+            .iter()
+            .map(|c| {
+                let [r, g, b, a] = c.to_linear_rgba();
+                Color::new(r, g, b, a)
+            })
+            .collect::<Vec<_>>();
+        LinearGradient::new(
+            &colors,
+            &linspace(dmin, dmax, n),
+            &[],
+            BlendMode::Rgb,
+            None,
+            HueDirection::Shorter,
+        )
+    }
+
     #[cfg_attr(
         feature = "preset",
         doc = r##"
@@ -210,12 +1169,729 @@ pub trait Gradient: CloneGradient {
     "##
     )]
     fn sharp(&self, segment: u16, smoothness: f32) -> SharpGradient {
+        self.sharp_in(segment, smoothness, BlendMode::Rgb)
+    }
+
+    /// Get new hard-edge gradient, the smooth transitions between segments are blended using `mode`
+    ///
+    /// [`sharp`](Gradient::sharp) of [`LinearGradient`], [`BasisGradient`] and
+    /// [`CatmullRomGradient`] use the blend mode of the gradient, other gradients use
+    /// [`BlendMode::Rgb`].
+    fn sharp_in(&self, segment: u16, smoothness: f32, mode: BlendMode) -> SharpGradient {
         let colors = if segment > 1 {
             self.colors(segment.into())
         } else {
             vec![self.at(self.domain().0), self.at(self.domain().0)]
         };
-        SharpGradient::new(&colors, self.domain(), smoothness)
+        SharpGradient::new(&colors, self.domain(), smoothness, mode)
+    }
+
+    /// Get new gradient resampled to `n` evenly spaced colors (at least 2), with smoothstep
+    /// transitions between them
+    ///
+    /// The transitions are blended using the blend mode of the gradient, if it has one.
+    fn smoothstepped(&self, n: usize) -> SmoothstepGradient {
+        let mode = self.mode().unwrap_or(BlendMode::Rgb);
+        SmoothstepGradient::new(&self.colors(n.max(2)), self.domain(), mode)
+    }
+
+    /// Get new gradient with every color's alpha multiplied by `factor`
+    ///
+    /// `factor` is clamped to `[0, 1]`.
+    fn scale_alpha(&self, factor: f32) -> AlphaScaleGradient {
+        AlphaScaleGradient::new(self.clone_gradient(), factor)
+    }
+
+    /// Get new gradient with every color's alpha multiplied by an alpha curve
+    ///
+    /// The curve is defined by `(position, alpha)` stops, in the gradient's domain,
+    /// independently of the color stops. The alpha is interpolated linearly between the
+    /// stops, and is the alpha of the nearest stop outside of them. Without stops the
+    /// colors are unchanged.
+    fn with_alpha_stops(&self, stops: &[(f32, f32)]) -> AlphaStopsGradient {
+        AlphaStopsGradient::new(self.clone_gradient(), stops)
+    }
+
+    /// Get new grayscale gradient of a single channel, useful to visualize it or to use it
+    /// as a mask
+    ///
+    /// The red, green and blue of every color are the channel value, the alpha is 1.
+    fn channel(&self, ch: Channel) -> ChannelGradient {
+        ChannelGradient::new(self.clone_gradient(), ch)
+    }
+
+    /// Convert into a reference counted gradient which is cheap to clone and can be
+    /// shared across threads
+    fn shared(self) -> SharedGradient
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        SharedGradient::new(std::sync::Arc::new(self))
+    }
+
+    /// Get new gradient which returns `under` for positions below the domain min and `over`
+    /// for positions above the domain max
+    fn with_bounds(&self, under: Color, over: Color) -> BoundedGradient {
+        BoundedGradient::new(self.clone_gradient(), under, over)
+    }
+
+    /// Get new gradient which returns `color` for NaN positions instead of opaque black
+    fn with_nan_color(&self, color: Color) -> NanColorGradient {
+        NanColorGradient::new(self.clone_gradient(), color)
+    }
+
+    /// Get new gradient applying a chain of effects, see [`GradientEffects`]
+    fn effects(&self) -> GradientEffects {
+        GradientEffects::new(self.clone_gradient())
+    }
+
+    /// Get new gradient with every color's hue rotated by `degrees` in Oklch color space
+    ///
+    /// Lightness and chroma are preserved.
+    fn shift_hue(&self, degrees: f32) -> HueShiftGradient {
+        HueShiftGradient::new(self.clone_gradient(), degrees)
+    }
+
+    /// Get new gradient with every color's lightness clamped into `[l_min, l_max]` in
+    /// Oklab color space, e.g. to avoid pure black and pure white for print
+    ///
+    /// The lightness range is within `[0, 1]`. Hue and chroma are preserved, the clamped
+    /// colors may be out of the sRGB gamut, see [`gamut_map_oklch`](Gradient::gamut_map_oklch).
+    fn clamp_lightness(&self, l_min: f32, l_max: f32) -> ClampLightnessGradient {
+        ClampLightnessGradient::new(self.clone_gradient(), l_min, l_max)
+    }
+
+    /// Get new gradient with out of sRGB gamut colors mapped into the gamut
+    ///
+    /// Chroma is reduced in Oklch color space until the color is representable, keeping
+    /// lightness and hue, following the
+    /// [CSS Color 4](https://www.w3.org/TR/css-color-4/#css-gamut-mapping) algorithm.
+    fn gamut_map_oklch(&self) -> GamutMapGradient {
+        GamutMapGradient::new(self.clone_gradient())
+    }
+
+    /// Get gnuplot `set palette defined` command using n colors evenly spaced across gradient
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(g.to_gnuplot(2), "set palette defined (0 1 0 0, 1 0 0 1)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_gnuplot(&self, n: usize) -> String {
+        let entries = linspace(0.0, 1.0, n)
+            .iter()
+            .zip(self.colors(n))
+            .map(|(t, c)| format!("{} {} {} {}", t, c.r, c.g, c.b))
+            .collect::<Vec<_>>();
+        format!("set palette defined ({})", entries.join(", "))
+    }
+
+    /// Get pgfplots `\pgfplotsset` colormap definition using n colors evenly spaced across
+    /// gradient, for use in LaTeX documents
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_pgfplots_colormap("redblue", 2),
+    ///     "\\pgfplotsset{colormap={redblue}{rgb255=(255,0,0) rgb255=(0,0,255)}}"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_pgfplots_colormap(&self, name: &str, n: usize) -> String {
+        let entries = self
+            .colors(n)
+            .iter()
+            .map(|c| {
+                let [r, g, b, _] = c.to_rgba8();
+                format!("rgb255=({},{},{})", r, g, b)
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "\\pgfplotsset{{colormap={{{}}}{{{}}}}}",
+            name,
+            entries.join(" ")
+        )
+    }
+
+    /// Get WGSL constant array of n (at least 2) linear-light RGBA colors evenly spaced
+    /// across gradient, followed by a `sample_<name>(t)` function interpolating it for `t`
+    /// in `[0, 1]`
+    ///
+    /// Useful to bake the gradient into a shader without a texture.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// let wgsl = g.to_wgsl_array("RED_BLUE", 2);
+    /// assert!(wgsl.starts_with(
+    ///     "const RED_BLUE: array<vec4<f32>, 2> = array<vec4<f32>, 2>(\n    \
+    ///     vec4<f32>(1.0, 0.0, 0.0, 1.0),\n    \
+    ///     vec4<f32>(0.0, 0.0, 1.0, 1.0),\n);\n"
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_wgsl_array(&self, name: &str, n: usize) -> String {
+        let n = n.max(2);
+        let mut s = format!(
+            "const {}: array<vec4<f32>, {}> = array<vec4<f32>, {}>(\n",
+            name, n, n
+        );
+
+        for c in self.colors(n) {
+            let [r, g, b, a] = c.to_linear_rgba();
+            s.push_str(&format!(
+                "    vec4<f32>({:?}, {:?}, {:?}, {:?}),\n",
+                r, g, b, a
+            ));
+        }
+
+        s.push_str(&format!(
+            ");\n\n\
+            fn sample_{}(t: f32) -> vec4<f32> {{\n    \
+                var colors = {};\n    \
+                let x = clamp(t, 0.0, 1.0) * {:?};\n    \
+                let i = u32(floor(x));\n    \
+                let j = min(i + 1u, {}u);\n    \
+                return mix(colors[i], colors[j], fract(x));\n\
+            }}\n",
+            name.to_lowercase(),
+            name,
+            (n - 1) as f32,
+            n - 1
+        ));
+
+        s
+    }
+
+    /// Get CSV of n colors evenly spaced across gradient, for spreadsheet QA
+    ///
+    /// The columns are the position, the red, green, blue and alpha in `[0, 1]`, the hex
+    /// color, and the lightness, chroma and hue (in degrees) in Oklch color space.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#000", "#fff"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// let csv = g.to_csv(2);
+    /// assert!(csv.starts_with("t,r,g,b,a,hex,L,C,h\n0,0,0,0,1,#000000,"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_csv(&self, n: usize) -> String {
+        let (dmin, dmax) = self.domain();
+        let mut s = String::from("t,r,g,b,a,hex,L,C,h\n");
+
+        for (t, c) in linspace(dmin, dmax, n).iter().zip(self.colors(n)) {
+            let [l, ch, h, _] = c.to_oklcha();
+            s.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                t,
+                c.r,
+                c.g,
+                c.b,
+                c.a,
+                c.to_css_hex(),
+                l,
+                ch,
+                h.to_degrees().rem_euclid(360.0)
+            ));
+        }
+
+        s
+    }
+
+    /// Get CSS `repeating-linear-gradient()` using n colors evenly spaced across gradient,
+    /// the stops pattern repeats every `period_percent`
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_css_repeating(3, 10.0),
+    ///     "repeating-linear-gradient(#ff0000 0%, #800080 5%, #0000ff 10%)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_css_repeating(&self, n: usize, period_percent: f32) -> String {
+        let stops = linspace(0.0, period_percent, n)
+            .iter()
+            .zip(self.colors(n))
+            .map(|(p, c)| format!("{} {}%", c.to_css_hex(), p))
+            .collect::<Vec<_>>();
+        format!("repeating-linear-gradient({})", stops.join(", "))
+    }
+
+    /// Get "nice" axis ticks across the domain, round numbers with steps of 1, 2 or 5
+    /// times a power of ten, each with its color
+    ///
+    /// The number of ticks is close to `target_count`, using the same algorithm as d3's
+    /// `ticks()`.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .domain(&[0.0, 97.0])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// let ticks = g.nice_ticks(5).iter().map(|(t, _)| *t).collect::<Vec<_>>();
+    /// assert_eq!(ticks, &[0.0, 20.0, 40.0, 60.0, 80.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn nice_ticks(&self, target_count: usize) -> Vec<(f32, Color)> {
+        let (dmin, dmax) = self.domain();
+        if target_count == 0 || dmax <= dmin {
+            return Vec::new();
+        }
+
+        let (dmin, dmax) = (f64::from(dmin), f64::from(dmax));
+        let step = (dmax - dmin) / target_count as f64;
+        let power = step.log10().floor();
+        let error = step / 10f64.powf(power);
+        let factor = if error >= 50f64.sqrt() {
+            10.0
+        } else if error >= 10f64.sqrt() {
+            5.0
+        } else if error >= 2f64.sqrt() {
+            2.0
+        } else {
+            1.0
+        };
+
+        // with a negative power, dividing by the inverse step is more precise
+        let inverse = power < 0.0;
+        let inc = if inverse {
+            10f64.powf(-power) / factor
+        } else {
+            factor * 10f64.powf(power)
+        };
+        let (i0, i1) = if inverse {
+            ((dmin * inc).ceil(), (dmax * inc).floor())
+        } else {
+            ((dmin / inc).ceil(), (dmax / inc).floor())
+        };
+
+        (i0 as i64..=i1 as i64)
+            .map(|i| {
+                let t = if inverse {
+                    i as f64 / inc
+                } else {
+                    i as f64 * inc
+                };
+                (t as f32, self.at(t as f32))
+            })
+            .collect()
+    }
+
+    /// Get n percentile breakpoints of `data`, each with the color of its value, for
+    /// quantile legends
+    ///
+    /// The breakpoints are the percentiles at `i / (n - 1)`, computed using linear
+    /// interpolation. The data range is mapped onto the gradient's domain to get the
+    /// colors. NaN values are ignored.
+    fn data_ticks(&self, data: &[f32], n: usize) -> Vec<(f32, Color)> {
+        let mut data = data
+            .iter()
+            .copied()
+            .filter(|v| !v.is_nan())
+            .collect::<Vec<_>>();
+
+        if data.is_empty() {
+            return Vec::new();
+        }
+
+        data.sort_by(|a, b| a.total_cmp(b));
+
+        let (dmin, dmax) = self.domain();
+        let (min, max) = (data[0], data[data.len() - 1]);
+        let last = (data.len() - 1) as f32;
+
+        linspace(0.0, 1.0, n)
+            .iter()
+            .map(|p| {
+                let i = p * last;
+                let (lo, hi) = (i.floor() as usize, i.ceil() as usize);
+                let v = data[lo] + (i - lo as f32) * (data[hi] - data[lo]);
+                let t = if max > min {
+                    (v - min) / (max - min)
+                } else {
+                    0.0
+                };
+                (v, self.at(dmin + t * (dmax - dmin)))
+            })
+            .collect()
+    }
+
+    /// Get a short identifier of n colors evenly spaced across gradient, e.g. for cache
+    /// file names
+    ///
+    /// The identifier is the 64-bit FNV-1a hash of the RGBA8 colors, hex encoded. It is
+    /// deterministic across runs and platforms.
+    fn color_signature(&self, n: usize) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+        for c in self.colors(n) {
+            for b in c.to_rgba8() {
+                hash ^= u64::from(b);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+
+        format!("{:016x}", hash)
+    }
+
+    /// Get a colored bar of `width` cells for terminals supporting 256 colors
+    ///
+    /// Every cell is a full block colored using the nearest xterm-256 palette color.
+    fn to_ansi_bar_256(&self, width: usize) -> String {
+        let mut s = String::new();
+
+        for c in self.colors(width) {
+            s.push_str(&format!("\x1b[38;5;{}m\u{2588}", xterm_256_index(&c)));
+        }
+
+        if width > 0 {
+            s.push_str("\x1b[0m");
+        }
+        s
+    }
+
+    /// Get Python snippet creating a matplotlib `ListedColormap` named `name` using n colors
+    /// evenly spaced across gradient
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_matplotlib("red_blue", 2),
+    ///     "from matplotlib.colors import ListedColormap\n\
+    ///     cmap = ListedColormap([(1, 0, 0), (0, 0, 1)], name=\"red_blue\")\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_matplotlib(&self, name: &str, n: usize) -> String {
+        let colors = self
+            .colors(n)
+            .iter()
+            .map(|c| format!("({}, {}, {})", c.r, c.g, c.b))
+            .collect::<Vec<_>>();
+        let name = name.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(
+            "from matplotlib.colors import ListedColormap\ncmap = ListedColormap([{}], name=\"{}\")\n",
+            colors.join(", "),
+            name
+        )
+    }
+
+    /// Get napari colormap JSON `{"name": [[r, g, b, a], ...]}` using n colors evenly spaced
+    /// across gradient
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#00f"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_napari("red_blue", 2),
+    ///     r#"{"red_blue":[[1,0,0,1],[0,0,1,1]]}"#
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_napari(&self, name: &str, n: usize) -> String {
+        let colors = self
+            .colors(n)
+            .iter()
+            .map(|c| format!("[{},{},{},{}]", c.r, c.g, c.b, c.a))
+            .collect::<Vec<_>>();
+        let name = name.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(r#"{{"{}":[{}]}}"#, name, colors.join(","))
+    }
+
+    /// Get Flutter `LinearGradient` Dart snippet using n colors evenly spaced across
+    /// gradient, the stops are normalized to `[0, 1]`
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0000ff80"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_flutter(2),
+    ///     "LinearGradient(colors: [Color(0xFFFF0000), Color(0x800000FF)], stops: [0.0, 1.0])"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_flutter(&self, n: usize) -> String {
+        let colors = self
+            .colors(n)
+            .iter()
+            .map(|c| {
+                let [r, g, b, a] = c.to_rgba8();
+                format!("Color(0x{:02X}{:02X}{:02X}{:02X})", a, r, g, b)
+            })
+            .collect::<Vec<_>>();
+        let stops = linspace(0.0, 1.0, n)
+            .iter()
+            .map(|t| format!("{:?}", t))
+            .collect::<Vec<_>>();
+        format!(
+            "LinearGradient(colors: [{}], stops: [{}])",
+            colors.join(", "),
+            stops.join(", ")
+        )
+    }
+
+    /// Get n colors evenly spaced across gradient as `(position, rgba)` stops, the position
+    /// normalized to `[0, 1]` and the RGBA8 color
+    ///
+    /// Common format of the colormaps of many plotting and visualization libraries.
+    fn export_stops_normalized(&self, n: usize) -> Vec<(f32, [u8; 4])> {
+        linspace(0.0, 1.0, n)
+            .into_iter()
+            .zip(self.colors(n))
+            .map(|(t, c)| (t, c.to_rgba8()))
+            .collect()
+    }
+
+    /// Get n colors evenly spaced across gradient as Qt `QGradientStop` values, the
+    /// position normalized to `[0, 1]` and the RGBA8 color
+    fn qt_stops(&self, n: usize) -> Vec<(f64, [u8; 4])> {
+        self.export_stops_normalized(n)
+            .into_iter()
+            .map(|(t, c)| (f64::from(t), c))
+            .collect()
+    }
+
+    /// Get SVG `<linearGradient>` element using n colors evenly spaced across gradient
+    ///
+    /// The stops offsets are percentages across the domain, `stop-opacity` is only
+    /// emitted for colors with alpha less than 1.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use colorgrad::Gradient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let g = colorgrad::GradientBuilder::new()
+    ///     .html_colors(&["#f00", "#0000ff80"])
+    ///     .build::<colorgrad::LinearGradient>()?;
+    ///
+    /// assert_eq!(
+    ///     g.to_svg_linear("grad", 2),
+    ///     "<linearGradient id=\"grad\">\
+    ///     <stop offset=\"0%\" stop-color=\"#ff0000\"/>\
+    ///     <stop offset=\"100%\" stop-color=\"#0000ff\" stop-opacity=\"0.5019608\"/>\
+    ///     </linearGradient>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_svg_linear(&self, id: &str, n: usize) -> String {
+        let id = id
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;");
+        let mut s = format!("<linearGradient id=\"{}\">", id);
+
+        for (t, c) in linspace(0.0, 100.0, n).iter().zip(self.colors(n)) {
+            let [r, g, b, _] = c.to_rgba8();
+            s.push_str(&format!(
+                "<stop offset=\"{}%\" stop-color=\"#{:02x}{:02x}{:02x}\"",
+                t, r, g, b
+            ));
+            if c.a < 1.0 {
+                s.push_str(&format!(" stop-opacity=\"{}\"", c.a));
+            }
+            s.push_str("/>");
+        }
+
+        s.push_str("</linearGradient>");
+        s
+    }
+}
+
+/// Create a two colors [`LinearGradient`] in the domain `[0, 1]`
+///
+/// Shortcut for the most common gradient, equivalent to building it using
+/// [`GradientBuilder`] with two colors.
+///
+/// ```
+/// use colorgrad::{BlendMode, Color, Gradient};
+///
+/// let g = colorgrad::two_color(
+///     Color::new(1.0, 0.0, 0.0, 1.0),
+///     Color::new(0.0, 0.0, 1.0, 1.0),
+///     BlendMode::Rgb,
+/// );
+///
+/// assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+/// assert_eq!(g.at(0.5).to_css_hex(), "#800080");
+/// assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
+/// ```
+pub fn two_color(start: Color, end: Color, mode: BlendMode) -> LinearGradient {
+    LinearGradient::new(
+        &[start, end],
+        &[0.0, 1.0],
+        &[],
+        mode,
+        None,
+        HueDirection::Shorter,
+    )
+}
+
+/// Blend several gradients, each with a weight, in the given blend mode
+///
+/// At every position, the colors of all the gradients are averaged using the weights,
+/// which are normalized. Every gradient is sampled at the same position relative to its
+/// domain, the domain of the blended gradient is the domain of the first gradient.
+///
+/// # Panics
+///
+/// Panics if `gradients` is empty.
+///
+/// ```
+/// use colorgrad::{BlendMode, Gradient};
+///
+/// let a = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#f00", "#00f"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap();
+/// let b = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#00f", "#f00"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap();
+///
+/// let g = colorgrad::blend_many(&[(&a, 3.0), (&b, 1.0)], BlendMode::Rgb);
+/// assert_eq!(g.at(0.0).to_css_hex(), "#bf0040");
+/// ```
+pub fn blend_many(gradients: &[(&dyn Gradient, f32)], mode: BlendMode) -> BlendManyGradient {
+    BlendManyGradient::new(gradients, mode)
+}
+
+/// Get `frames` gradients blending from `a` to `b` in the given blend mode, for
+/// transition animations
+///
+/// The first frame is `a`, the last frame is `b`. Every frame is blended like
+/// [`blend_many`] and baked into a [`LinearGradient`] of 256 colors in the domain of `a`,
+/// so sampling a frame is cheap.
+///
+/// ```
+/// use colorgrad::{BlendMode, Gradient};
+///
+/// let a = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#f00", "#f00"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap();
+/// let b = colorgrad::GradientBuilder::new()
+///     .html_colors(&["#00f", "#00f"])
+///     .build::<colorgrad::LinearGradient>()
+///     .unwrap();
+///
+/// let frames = colorgrad::crossfade(&a, &b, 3, BlendMode::Rgb);
+/// assert_eq!(frames[1].at(0.5).to_css_hex(), "#800080");
+/// ```
+pub fn crossfade(
+    a: &dyn Gradient,
+    b: &dyn Gradient,
+    frames: usize,
+    mode: BlendMode,
+) -> Vec<LinearGradient> {
+    let last = frames.saturating_sub(1).max(1) as f32;
+    (0..frames)
+        .map(|i| {
+            let w = i as f32 / last;
+            blend_many(&[(a, 1.0 - w), (b, w)], mode).to_lut(256)
+        })
+        .collect()
+}
+
+/// Get a function mapping axial hex coordinates `(q, r)` to a color, for hex grid maps
+///
+/// The coordinates are converted to the cartesian position of the hex center (pointy top,
+/// the distance between adjacent centers is 1), `x = q + r / 2` and `y = r * sqrt(3) / 2`.
+/// The color is the average, in [`BlendMode::Rgb`], of `gx` at `x` and `gy` at `y`.
+///
+/// ```
+/// use colorgrad::{BlendMode, Color};
+///
+/// let gx = colorgrad::two_color(
+///     Color::new(1.0, 0.0, 0.0, 1.0),
+///     Color::new(0.0, 0.0, 1.0, 1.0),
+///     BlendMode::Rgb,
+/// );
+/// let gy = colorgrad::two_color(
+///     Color::new(1.0, 0.0, 0.0, 1.0),
+///     Color::new(0.0, 1.0, 0.0, 1.0),
+///     BlendMode::Rgb,
+/// );
+///
+/// let field = colorgrad::hex_field(&gx, &gy);
+/// assert_eq!(field(0, 0).to_css_hex(), "#ff0000");
+/// ```
+pub fn hex_field<'a>(
+    gx: &'a dyn Gradient,
+    gy: &'a dyn Gradient,
+) -> impl Fn(i32, i32) -> Color + 'a {
+    move |q, r| {
+        let x = q as f32 + r as f32 / 2.0;
+        let y = r as f32 * 3f32.sqrt() / 2.0;
+        gx.at(x).interpolate_rgb(&gy.at(y), 0.5)
     }
 }
 
@@ -238,17 +1914,197 @@ impl Clone for Box<dyn Gradient> {
     }
 }
 
-fn convert_colors(colors: &[Color], mode: BlendMode) -> Vec<[f32; 4]> {
-    colors
-        .iter()
-        .map(|c| match mode {
-            BlendMode::Rgb => c.to_array(),
-            BlendMode::LinearRgb => c.to_linear_rgba(),
-            BlendMode::Oklab => c.to_oklaba(),
-            #[cfg(feature = "lab")]
-            BlendMode::Lab => c.to_laba(),
-        })
-        .collect()
+// Convert colors for blending in `mode`, see `convert_color`. Returns an iterator so the
+// gradients can collect the values straight into their own storage.
+fn convert_colors<'a>(
+    colors: &'a [Color],
+    mode: BlendMode,
+    hue: HueDirection,
+) -> impl Iterator<Item = [f32; 4]> + 'a {
+    let k = hue_channel(mode);
+    // The Lch chroma of grays is not exactly zero because of rounding errors.
+    let achromatic = move |v: &[f32; 4]| match mode {
+        BlendMode::Hsl => v[1] <= 0.0,
+        #[cfg(feature = "lab")]
+        BlendMode::Lch => v[1] < 1e-2,
+        _ => false,
+    };
+
+    // Achromatic colors have no hue, use the hue of the previous (or next) color to not
+    // sweep the hue while fading from or to gray.
+    let first_hue = k.and_then(|k| {
+        colors
+            .iter()
+            .map(|c| convert_color(c, mode))
+            .find(|v| !achromatic(v))
+            .map(|v| v[k])
+    });
+    let mut prev: Option<[f32; 4]> = None;
+
+    colors.iter().map(move |c| {
+        let mut v = convert_color(c, mode);
+        if let Some(k) = k {
+            if achromatic(&v) {
+                if let Some(h) = prev.map(|p| p[k]).or(first_hue) {
+                    v[k] = h;
+                }
+            }
+            // Unwrap the hues so that interpolating between two consecutive colors goes
+            // in the hue direction.
+            if let Some(p) = prev {
+                v[k] = p[k] + hue_delta(p[k], v[k], hue);
+            }
+            prev = Some(v);
+        }
+        v
+    })
+}
+
+// Index of the hue channel, in degrees, of the hue based blend modes.
+fn hue_channel(mode: BlendMode) -> Option<usize> {
+    match mode {
+        BlendMode::Hsl => Some(0),
+        #[cfg(feature = "lab")]
+        BlendMode::Lch => Some(2),
+        _ => None,
+    }
+}
+
+// Signed difference, in degrees, to go from hue h0 to hue h1 in the given direction.
+// Equal hues never make a full turn.
+fn hue_delta(h0: f32, h1: f32, dir: HueDirection) -> f32 {
+    let d = (h1 - h0).rem_euclid(360.0);
+    if d == 0.0 {
+        return 0.0;
+    }
+    match dir {
+        HueDirection::Shorter if d > 180.0 => d - 360.0,
+        HueDirection::Longer if d <= 180.0 => d - 360.0,
+        HueDirection::Decreasing => d - 360.0,
+        _ => d,
+    }
+}
+
+#[inline]
+fn convert_color(c: &Color, mode: BlendMode) -> [f32; 4] {
+    match mode {
+        BlendMode::Rgb => c.to_array(),
+        BlendMode::LinearRgb => c.to_linear_rgba(),
+        BlendMode::Oklab => c.to_oklaba(),
+        BlendMode::Hsl => c.to_hsla(),
+        #[cfg(feature = "lab")]
+        BlendMode::Lab => c.to_laba(),
+        #[cfg(feature = "lab")]
+        BlendMode::Lch => {
+            let [l, c, h, a] = c.to_lcha();
+            [l, c, h.to_degrees(), a]
+        }
+    }
+}
+
+#[inline]
+fn to_color(v: &[f32; 4], mode: BlendMode) -> Color {
+    let [a, b, c, d] = *v;
+    match mode {
+        BlendMode::Rgb => Color::new(a, b, c, d),
+        BlendMode::LinearRgb => Color::from_linear_rgba(a, b, c, d),
+        BlendMode::Oklab => Color::from_oklaba(a, b, c, d),
+        BlendMode::Hsl => Color::from_hsla(a, b, c, d),
+        #[cfg(feature = "lab")]
+        BlendMode::Lab => Color::from_laba(a, b, c, d),
+        #[cfg(feature = "lab")]
+        BlendMode::Lch => Color::from_lcha(a, b, c.to_radians(), d),
+    }
+}
+
+// Euclidean distance of two Oklab colors, the alpha is ignored
+pub(crate) fn oklab_distance(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+// WCAG relative luminance
+fn relative_luminance(c: &Color) -> f32 {
+    let [r, g, b, _] = c.to_linear_rgba();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+// Value of a single channel of c, see `Gradient::channel`
+fn channel_value(c: &Color, ch: Channel) -> f32 {
+    match ch {
+        Channel::R => c.r,
+        Channel::G => c.g,
+        Channel::B => c.b,
+        Channel::A => c.a,
+        Channel::Luminance => relative_luminance(c),
+    }
+}
+
+// Index of the xterm-256 palette color nearest to c, among the 6x6x6 color cube and the
+// grayscale ramp. The 16 system colors are not used, they depend on the terminal theme.
+fn xterm_256_index(c: &Color) -> u8 {
+    const LEVELS: [i32; 6] = [0, 95, 135, 175, 215, 255];
+
+    let [r, g, b, _] = c.to_rgba8().map(i32::from);
+    let dist = |x: [i32; 3]| (x[0] - r).pow(2) + (x[1] - g).pow(2) + (x[2] - b).pow(2);
+
+    let nearest_level = |v: i32| {
+        (0..6)
+            .min_by_key(|&i| (LEVELS[i] - v).abs())
+            .unwrap_or_default()
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = dist([LEVELS[ri], LEVELS[gi], LEVELS[bi]]);
+
+    // gray levels are 8, 18, .., 238
+    let k = ((r + g + b) / 3 - 3).clamp(0, 230) / 10;
+    let gray = 8 + k * 10;
+
+    if dist([gray; 3]) < cube {
+        232 + k as u8
+    } else {
+        (16 + 36 * ri + 6 * gi + bi) as u8
+    }
+}
+
+// Whether the values are either non-decreasing or non-increasing, ignoring changes
+// smaller than a small tolerance. Changes are measured from the lowest and highest
+// value seen so far, so a slow reversal spread over many samples is still detected.
+fn is_monotone(values: &[f32]) -> bool {
+    const TOLERANCE: f32 = 1e-3;
+
+    let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+    let (mut up, mut down) = (false, false);
+    for &v in values {
+        min = min.min(v);
+        max = max.max(v);
+        up |= v - min > TOLERANCE;
+        down |= max - v > TOLERANCE;
+    }
+
+    !(up && down)
+}
+
+// Index of the stop nearest to t, given n sorted stop positions.
+fn nearest_stop(n: usize, t: f32, pos: impl Fn(usize) -> f32) -> usize {
+    let mut low = 0;
+    let mut high = n;
+
+    while low < high {
+        let mid = (low + high) / 2;
+        if pos(mid) < t {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == 0 {
+        0
+    } else if low == n || t - pos(low - 1) <= pos(low) - t {
+        low - 1
+    } else {
+        low
+    }
 }
 
 fn linspace(min: f32, max: f32, n: usize) -> Vec<f32> {
@@ -0,0 +1,49 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use colorgrad::{BlendMode, Color, GradientBuilder, LinearGradient};
+
+// Count the allocations of the current thread, the tests run in parallel.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocs<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = ALLOCS.with(Cell::get);
+    let res = f();
+    (res, ALLOCS.with(Cell::get) - start)
+}
+
+#[test]
+fn linear_gradient_build_no_alloc() {
+    let colors = (0..8)
+        .map(|i| Color::from_hsla(i as f32 * 45.0, 1.0, 0.5, 1.0))
+        .collect::<Vec<_>>();
+
+    for mode in [BlendMode::Rgb, BlendMode::Oklab, BlendMode::Hsl] {
+        let mut gb = GradientBuilder::new();
+        gb.colors(&colors).mode(mode);
+        // the first build prepares the builder
+        gb.build::<LinearGradient>().unwrap();
+
+        let (grad, n) = allocs(|| gb.build::<LinearGradient>().unwrap());
+        assert_eq!(n, 0, "{:?}", mode);
+        drop(grad);
+    }
+}
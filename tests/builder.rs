@@ -1,5 +1,6 @@
 use colorgrad::{
-    BlendMode, Color, Gradient, GradientBuilder, GradientBuilderError, LinearGradient,
+    BasisGradient, BlendMode, CatmullRomGradient, ChannelMode, Color, Gradient, GradientBuilder,
+    GradientBuilderError, GradientBuilderWarning, HueDirection, LinearGradient,
 };
 
 mod utils;
@@ -11,8 +12,8 @@ fn builder() {
 
     let g = GradientBuilder::new().build::<LinearGradient>().unwrap();
     assert_eq!(g.domain(), (0.0, 1.0));
-    assert_eq!(g.at(0.0).to_hex_string(), "#000000");
-    assert_eq!(g.at(1.0).to_hex_string(), "#ffffff");
+    assert_eq!(g.at(0.0).to_css_hex(), "#000000");
+    assert_eq!(g.at(1.0).to_css_hex(), "#ffffff");
 
     // Single color
 
@@ -30,9 +31,9 @@ fn builder() {
         .html_colors(&["#f00", "#0f0", "#00f"])
         .build::<LinearGradient>()
         .unwrap();
-    assert_eq!(g.at(0.0).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(0.5).to_hex_string(), "#00ff00");
-    assert_eq!(g.at(1.0).to_hex_string(), "#0000ff");
+    assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.5).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
 
     // Custom domain
 
@@ -41,9 +42,9 @@ fn builder() {
         .domain(&[-100.0, 100.0])
         .build::<LinearGradient>()
         .unwrap();
-    assert_eq!(g.at(-100.0).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(0.0).to_hex_string(), "#00ff00");
-    assert_eq!(g.at(100.0).to_hex_string(), "#0000ff");
+    assert_eq!(g.at(-100.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.0).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(100.0).to_css_hex(), "#0000ff");
 
     // Color position
 
@@ -52,10 +53,10 @@ fn builder() {
         .domain(&[13.0, 27.3, 90.0, 97.5])
         .build::<LinearGradient>()
         .unwrap();
-    assert_eq!(g.at(13.0).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(27.3).to_hex_string(), "#00ff00");
-    assert_eq!(g.at(90.0).to_hex_string(), "#0000ff");
-    assert_eq!(g.at(97.5).to_hex_string(), "#ff00ff");
+    assert_eq!(g.at(13.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(27.3).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(90.0).to_css_hex(), "#0000ff");
+    assert_eq!(g.at(97.5).to_css_hex(), "#ff00ff");
 
     // Multiple colors, custom domain
 
@@ -69,7 +70,7 @@ fn builder() {
     let g = gb.build::<LinearGradient>().unwrap();
 
     assert_eq!(
-        &colors2hex(&gb.get_colors()),
+        &colors2hex(gb.get_colors()),
         &["#0000ff", "#00ffff", "#ffff0080", "#00ff00"]
     );
     assert_eq!(
@@ -86,7 +87,7 @@ fn builder() {
 
     assert_eq!(&gb.get_positions(), &[0.0, 0.5, 0.5, 1.0]);
     assert_eq!(
-        &colors2hex(&gb.get_colors()),
+        &colors2hex(gb.get_colors()),
         &["#ff0000", "#0000ff", "#000000", "#ffffff"]
     );
 
@@ -155,6 +156,21 @@ fn css_gradient() {
             vec![0.0, 15.0],
             vec!["#00ff00", "#0000ff"],
         ),
+        (
+            "red /* start */, blue",
+            vec![0.0, 1.0],
+            vec!["#ff0000", "#0000ff"],
+        ),
+        (
+            "/* from */ red, lime/* mid */ 75%, /**/blue /* end",
+            vec![0.0, 0.75, 1.0],
+            vec!["#ff0000", "#00ff00", "#0000ff"],
+        ),
+        (
+            "rgb(255, /* g */ 0, 0) 0%, /* hint */ #00f 100%",
+            vec![0.0, 1.0],
+            vec!["#ff0000", "#0000ff"],
+        ),
     ];
 
     for (s, positions, colors) in test_data {
@@ -173,6 +189,7 @@ fn css_gradient() {
         "red, lime, 100%",
         "deeppink, 0.4, 0.9, pink",
         "0%, 100%",
+        "/* red, blue */",
     ];
 
     for s in invalid_css {
@@ -236,4 +253,662 @@ fn builder_error() {
         .domain(&[0.0, 0.0, 0.0])
         .build::<LinearGradient>();
     assert_eq!(g.unwrap_err(), GradientBuilderError::InvalidStops);
+
+    // Channel modes are only supported by LinearGradient
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#00f"]).channel_modes([
+        ChannelMode::Circular,
+        ChannelMode::Linear,
+        ChannelMode::Linear,
+    ]);
+    assert_eq!(
+        gb.build::<BasisGradient>().unwrap_err(),
+        GradientBuilderError::UnsupportedChannelModes
+    );
+    assert_eq!(
+        gb.build::<CatmullRomGradient>().unwrap_err(),
+        GradientBuilderError::UnsupportedChannelModes
+    );
+    assert!(gb.build::<LinearGradient>().is_ok());
+}
+
+#[test]
+fn try_html_colors() {
+    let mut gb = GradientBuilder::new();
+    let res = gb.try_html_colors(&["#777", "bloodred", "gold", "#zzz", "rgb(0,0,255)"]);
+    assert_eq!(res.len(), 5);
+    assert_eq!(res[0], Ok(()));
+    assert_eq!(
+        res[1],
+        Err(Color::from_html("bloodred").unwrap_err().to_string())
+    );
+    assert_eq!(res[2], Ok(()));
+    assert!(res[3].is_err());
+    assert_eq!(res[4], Ok(()));
+    assert_eq!(
+        colors2hex(gb.get_colors()),
+        &["#777777", "#ffd700", "#0000ff"]
+    );
+
+    // failures are still recorded for the build error
+    assert_eq!(
+        gb.build::<LinearGradient>().unwrap_err(),
+        GradientBuilderError::InvalidHtmlColors(vec!["bloodred".to_string(), "#zzz".to_string()])
+    );
+
+    let mut gb = GradientBuilder::new();
+    assert!(gb
+        .try_html_colors(&["red", "blue"])
+        .iter()
+        .all(Result::is_ok));
+    assert!(gb.build::<LinearGradient>().is_ok());
+}
+
+#[test]
+fn css_gradient_hint() {
+    let mut gb = GradientBuilder::new();
+    let g = gb.css("red, 25%, blue").build::<LinearGradient>().unwrap();
+
+    assert_eq!(gb.get_positions(), &[0.0, 1.0]);
+    assert_eq!(&colors2hex(gb.get_colors()), &["#ff0000", "#0000ff"]);
+
+    // reference values rendered by browsers
+    let data = [
+        (0.0, "#ff0000"),
+        (0.0625, "#bf0040"),
+        (0.25, "#800080"),
+        (0.5, "#4b00b4"),
+        (0.75, "#2200dd"),
+        (1.0, "#0000ff"),
+    ];
+    for (t, hex) in data {
+        assert_eq!(g.at(t).to_css_hex(), hex);
+    }
+
+    // hint in the middle is a plain linear transition
+    let g = GradientBuilder::new()
+        .css("red, 50%, blue")
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.25).to_css_hex(), "#bf0040");
+
+    // hint at the edges
+    let g = GradientBuilder::new()
+        .css("red, lime 50%, 50%, blue")
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.25).to_css_hex(), "#808000");
+    assert_eq!(g.at(0.51).to_css_hex(), "#0000ff");
+
+    let g = GradientBuilder::new()
+        .css("red, 100%, blue")
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.99).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
+
+    // custom domain
+    let g = GradientBuilder::new()
+        .css("red, 25%, blue")
+        .domain(&[0.0, 100.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(25.0).to_css_hex(), "#800080");
+
+    // gradients without hint support place the midpoint color at the hint
+    let g = GradientBuilder::new()
+        .css("red, 25%, blue")
+        .build::<colorgrad::CatmullRomGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.25).to_css_hex(), "#800080");
+}
+
+#[test]
+fn build_with_mode() {
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(BlendMode::LinearRgb);
+
+    let rgb = gb
+        .build_with_mode::<LinearGradient>(BlendMode::Rgb)
+        .unwrap();
+    assert_eq!(rgb.mode(), Some(BlendMode::Rgb));
+    assert_eq!(rgb.at(0.25).to_css_hex(), "#808000");
+
+    let oklab = gb
+        .build_with_mode::<LinearGradient>(BlendMode::Oklab)
+        .unwrap();
+    assert_eq!(oklab.mode(), Some(BlendMode::Oklab));
+    assert_eq!(
+        oklab.at(0.25).to_css_hex(),
+        Color::from_html("#f00")
+            .unwrap()
+            .interpolate_oklab(&Color::from_html("#0f0").unwrap(), 0.5)
+            .to_css_hex()
+    );
+
+    #[cfg(feature = "lab")]
+    {
+        let lab = gb
+            .build_with_mode::<colorgrad::CatmullRomGradient>(BlendMode::Lab)
+            .unwrap();
+        assert_eq!(lab.mode(), Some(BlendMode::Lab));
+        assert_eq!(lab.at(0.0).to_css_hex(), "#ff0000");
+    }
+
+    // the builder's mode is still used by build()
+    let g = gb.build::<LinearGradient>().unwrap();
+    assert_eq!(g.mode(), Some(BlendMode::LinearRgb));
+    assert_eq!(g.at(0.25).to_css_hex(), "#bcbc00");
+
+    // errors
+    let mut gb = GradientBuilder::new();
+    gb.css("#f00, #zzz").mode(BlendMode::LinearRgb);
+    assert!(gb
+        .build_with_mode::<LinearGradient>(BlendMode::Oklab)
+        .is_err());
+    let g = gb.css("#f00, #00f").build::<LinearGradient>().unwrap();
+    assert_eq!(g.mode(), Some(BlendMode::LinearRgb));
+}
+
+#[test]
+fn domain_unit() {
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 100.0]);
+
+    let g = gb.build::<LinearGradient>().unwrap();
+    assert_eq!(g.domain(), (0.0, 100.0));
+
+    let g = gb.domain_unit().build::<LinearGradient>().unwrap();
+    assert_eq!(g.domain(), (0.0, 1.0));
+    assert_eq!(gb.get_positions(), &[0.0, 0.5, 1.0]);
+    assert_eq!(g.at(0.5).to_css_hex(), "#00ff00");
+
+    // after a build, the computed positions are cleared too
+    let g = gb.domain(&[-5.0, 5.0]).build::<LinearGradient>().unwrap();
+    assert_eq!(g.domain(), (-5.0, 5.0));
+    let g = gb.domain_unit().build::<LinearGradient>().unwrap();
+    assert_eq!(g.domain(), (0.0, 1.0));
+}
+
+#[test]
+fn position_curve() {
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .position_curve(2.0)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 0.25, 1.0]);
+    assert_eq!(g.at(0.25).to_css_hex(), "#00ff00");
+
+    // custom domain
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 3.0])
+        .position_curve(2.0)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[-1.0, 0.0, 3.0]);
+
+    // explicit positions are not affected
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.7, 1.0])
+        .position_curve(2.0)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 0.7, 1.0]);
+
+    for power in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+        let g = GradientBuilder::new()
+            .html_colors(&["#f00", "#0f0", "#00f"])
+            .position_curve(power)
+            .build::<LinearGradient>();
+        assert_eq!(g.unwrap_err(), GradientBuilderError::InvalidDomain);
+    }
+}
+
+#[test]
+fn dedup_adjacent() {
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .html_colors(&["red", "red", "blue"])
+        .dedup_adjacent()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(colors2hex(gb.get_colors()), &["#ff0000", "#0000ff"]);
+    assert_eq!(gb.get_positions(), &[0.0, 1.0]);
+    assert_eq!(g.at(0.5).to_css_hex(), "#800080");
+
+    // without dedup
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .html_colors(&["red", "red", "blue"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_colors().len(), 3);
+    assert_eq!(g.at(0.5).to_css_hex(), "#ff0000");
+
+    // the domain is preserved
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["red", "red", "lime", "blue", "blue", "blue"])
+        .domain(&[0.0, 0.2, 0.4, 0.6, 0.8, 1.0])
+        .dedup_adjacent()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(
+        colors2hex(gb.get_colors()),
+        &["#ff0000", "#00ff00", "#0000ff"]
+    );
+    assert_eq!(gb.get_positions(), &[0.0, 0.4, 1.0]);
+
+    // hints of the merged segments
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .html_colors(&["red", "red", "blue"])
+        .hints(&[Some(0.9), Some(0.25)])
+        .dedup_adjacent()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 1.0]);
+    assert_eq!(g.at(0.25).to_css_hex(), "#800080");
+
+    // a single color
+    let g = GradientBuilder::new()
+        .html_colors(&["gold", "gold"])
+        .dedup_adjacent()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.5).to_css_hex(), "#ffd700");
+}
+
+#[test]
+fn reverse() {
+    let mut gb = GradientBuilder::new();
+    gb.css("gold, red 20%, #00f, #0f08 90%, deeppink")
+        .domain(&[-3.0, 7.0]);
+
+    let g = gb.clone().build::<LinearGradient>().unwrap();
+    let gr = gb.reverse().build::<LinearGradient>().unwrap();
+
+    assert_eq!(gr.domain(), (-3.0, 7.0));
+    assert_eq!(
+        &colors2hex(gb.get_colors()),
+        &["#ff1493", "#00ff0088", "#0000ff", "#ff0000", "#ffd700"]
+    );
+
+    for i in 0..=100 {
+        let t = -3.0 + i as f32 / 10.0;
+        let a = g.at(4.0 - t).to_array();
+        let b = gr.at(t).to_array();
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    // the midpoint of a transition stays at the mirrored hint
+    let g = GradientBuilder::new()
+        .css("#f00, 30%, #00f")
+        .reverse()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.7).to_css_hex(), "#800080");
+
+    // reverse twice
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.2, 1.0])
+        .reverse()
+        .reverse()
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 0.2, 1.0]);
+
+    // rebuilding doesn't reverse again
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.2, 1.0])
+        .reverse()
+        .build::<LinearGradient>()
+        .unwrap();
+    gb.mode(BlendMode::Oklab).build::<LinearGradient>().unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 0.8, 1.0]);
+    assert_eq!(
+        &colors2hex(gb.get_colors()),
+        &["#0000ff", "#00ff00", "#ff0000"]
+    );
+}
+
+#[test]
+fn warn_non_monotone() {
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(
+        gb.warnings(),
+        &[GradientBuilderWarning::NonMonotoneLightness]
+    );
+    assert_eq!(gb.warnings()[0].to_string(), "non-monotone lightness");
+
+    // monotone, in both directions
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#000", "#5a0", "#ff0"])
+        .mode(BlendMode::Oklab)
+        .warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(gb.warnings().is_empty());
+    gb.reverse().build::<LinearGradient>().unwrap();
+    assert!(gb.warnings().is_empty());
+
+    // shallow dip spread over many samples
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#000", "#888", "#808080", "#fff"])
+        .domain(&[0.0, 0.01, 0.99, 1.0])
+        .warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(
+        gb.warnings(),
+        &[GradientBuilderWarning::NonMonotoneLightness]
+    );
+
+    // the spline overshoots between stops with monotone lightness
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#504dd4", "#6b7f4b", "#cee460"])
+        .warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(gb.warnings().is_empty());
+    gb.build::<CatmullRomGradient>().unwrap();
+    assert_eq!(
+        gb.warnings(),
+        &[GradientBuilderWarning::NonMonotoneLightness]
+    );
+
+    // changing the hue direction checks again
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#f0f"])
+        .mode(BlendMode::Hsl)
+        .warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(gb.warnings().is_empty());
+    gb.hue_direction(HueDirection::Longer)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.warnings().len(), 1);
+
+    // disabled by default
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(gb.warnings().is_empty());
+
+    gb.warn_non_monotone(true)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.warnings().len(), 1);
+
+    gb.reset();
+    assert!(gb.warnings().is_empty());
+}
+
+#[test]
+fn colors_rgba8() {
+    let g1 = GradientBuilder::new()
+        .colors_rgba8(&[[255, 0, 0, 255], [0, 0, 255, 255]])
+        .build::<LinearGradient>()
+        .unwrap();
+    let g2 = GradientBuilder::new()
+        .html_colors(&["#ff0000", "#0000ff"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    for i in 0..=10 {
+        let t = i as f32 / 10.0;
+        assert_eq!(g1.at(t).to_array(), g2.at(t).to_array());
+    }
+
+    let mut gb = GradientBuilder::new();
+    gb.colors_rgba8(&[[0, 128, 0, 64]])
+        .colors_rgba8(&[[10, 20, 30, 255], [255, 255, 255, 0]]);
+    assert_eq!(
+        colors2hex(gb.get_colors()),
+        &["#00800040", "#0a141e", "#ffffff00"]
+    );
+}
+
+#[test]
+fn hints() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .hints(&[Some(0.25)])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.25).to_css_hex(), "#800080");
+    assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
+
+    // same as css hint
+    let g2 = GradientBuilder::new()
+        .css("#f00, 25%, #00f")
+        .build::<LinearGradient>()
+        .unwrap();
+    for i in 0..=20 {
+        let t = i as f32 / 20.0;
+        assert_eq!(g.at(t).to_rgba8(), g2.at(t).to_rgba8());
+    }
+
+    // per segment, relative to the segment
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f", "#0f0"])
+        .domain(&[0.0, 10.0])
+        .hints(&[None, Some(0.8)])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(2.5).to_css_hex(), "#800080");
+    assert_eq!(g.at(9.0).to_css_hex(), "#008080");
+
+    // other gradients place the midpoint color at the hint
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .hints(&[Some(0.25), Some(0.9)])
+        .build::<colorgrad::CatmullRomGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.25).to_css_hex(), "#800080");
+
+    // clamped
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#00f"])
+        .hints(&[Some(-1.0)])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(
+        gb.build::<LinearGradient>().unwrap().at(0.5).to_css_hex(),
+        "#0000ff"
+    );
+}
+
+#[cfg(feature = "preset")]
+#[test]
+fn sample_from() {
+    let viridis = colorgrad::preset::viridis();
+    let custom = GradientBuilder::new()
+        .html_colors(&["#f00", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .sample_from(&viridis, 5, 0.0, 0.5)
+        .sample_from(&custom, 3, 0.5, 1.0)
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        gb.get_positions(),
+        &[0.0, 0.125, 0.25, 0.375, 0.5, 0.5, 0.75, 1.0]
+    );
+
+    for (i, c) in viridis.colors(5).iter().enumerate() {
+        assert_eq!(g.at(i as f32 * 0.125).to_rgba8(), c.to_rgba8());
+    }
+    assert_eq!(g.at(0.5).to_rgba8(), viridis.at(1.0).to_rgba8());
+    assert_eq!(g.at(0.5001).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.75).to_css_hex(), "#ff8080");
+    assert_eq!(g.at(1.0).to_css_hex(), "#ffffff");
+}
+
+#[test]
+fn measured() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .measured(&[
+            (25.0, red.clone()),
+            (-5.0, blue.clone()),
+            (10.0, white.clone()),
+        ])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.domain(), (-5.0, 25.0));
+    assert_eq!(gb.get_positions(), &[-5.0, 10.0, 25.0]);
+    assert_eq!(
+        &colors2hex(gb.get_colors()),
+        &["#0000ff", "#ffffff", "#ff0000"]
+    );
+    assert_eq!(g.at(-5.0).to_css_hex(), "#0000ff");
+    assert_eq!(g.at(2.5).to_css_hex(), "#8080ff");
+    assert_eq!(g.at(10.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.at(17.5).to_css_hex(), "#ff8080");
+    assert_eq!(g.at(25.0).to_css_hex(), "#ff0000");
+
+    // repeated value
+    let res = GradientBuilder::new()
+        .measured(&[
+            (1.0, red.clone()),
+            (0.0, blue.clone()),
+            (1.0, white.clone()),
+        ])
+        .build::<LinearGradient>();
+    assert_eq!(res.unwrap_err(), GradientBuilderError::InvalidDomain);
+
+    // not finite
+    let res = GradientBuilder::new()
+        .measured(&[(0.0, red.clone()), (f32::NAN, blue.clone())])
+        .build::<LinearGradient>();
+    assert_eq!(res.unwrap_err(), GradientBuilderError::InvalidDomain);
+
+    // valid data replaces invalid data
+    let mut gb = GradientBuilder::new();
+    gb.measured(&[(0.0, red.clone()), (0.0, blue.clone())]);
+    assert!(gb.build::<LinearGradient>().is_err());
+    gb.measured(&[(0.0, red.clone()), (1.0, blue.clone())]);
+    assert!(gb.build::<LinearGradient>().is_ok());
+
+    // replacing the positions clears the error
+    let mut gb = GradientBuilder::new();
+    gb.measured(&[(0.0, red.clone()), (0.0, blue.clone())]);
+    assert!(gb.build::<LinearGradient>().is_err());
+    gb.domain(&[0.0, 1.0]);
+    assert!(gb.build::<LinearGradient>().is_ok());
+
+    gb.measured(&[(f32::NAN, red.clone()), (0.0, blue.clone())]);
+    assert!(gb.build::<LinearGradient>().is_err());
+    gb.css("#f00, #00f");
+    assert!(gb.build::<LinearGradient>().is_ok());
+
+    gb.measured(&[(f32::NAN, red.clone()), (0.0, blue.clone())]);
+    gb.domain_unit();
+    assert!(gb.build::<LinearGradient>().is_ok());
+
+    gb.measured(&[(f32::NAN, red), (0.0, blue)]);
+    gb.from_gradient(&g, 5);
+    assert!(gb.build::<LinearGradient>().is_ok());
+}
+
+#[test]
+fn insert_stop() {
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#00f"]).domain(&[0.0, 100.0]);
+    let g = gb.build::<LinearGradient>().unwrap();
+    assert_eq!(g.at(25.0).to_css_hex(), "#bf0040");
+
+    let g = gb
+        .insert_stop(25.0, white.clone())
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 25.0, 100.0]);
+    assert_eq!(
+        &colors2hex(gb.get_colors()),
+        &["#ff0000", "#ffffff", "#0000ff"]
+    );
+    assert_eq!(g.domain(), (0.0, 100.0));
+    assert_eq!(g.at(0.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(25.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.at(100.0).to_css_hex(), "#0000ff");
+
+    // outside the current domain
+    let g = gb
+        .insert_stop(-50.0, Color::new(0.0, 0.0, 0.0, 1.0))
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[-50.0, 0.0, 25.0, 100.0]);
+    assert_eq!(g.domain(), (-50.0, 100.0));
+    assert_eq!(g.at(-50.0).to_css_hex(), "#000000");
+
+    // positions not explicit
+    let res = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .insert_stop(0.5, white.clone())
+        .build::<LinearGradient>();
+    assert_eq!(res.unwrap_err(), GradientBuilderError::InvalidDomain);
+
+    // not finite
+    let res = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .domain(&[0.0, 1.0])
+        .insert_stop(f32::NAN, white.clone())
+        .build::<LinearGradient>();
+    assert_eq!(res.unwrap_err(), GradientBuilderError::InvalidDomain);
+
+    // recover after an invalid insert
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .insert_stop(0.5, white.clone());
+    assert!(gb.build::<LinearGradient>().is_err());
+    let g = gb
+        .domain(&[0.0, 0.5, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.5).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(1.0).to_css_hex(), "#0000ff");
+
+    // and inserting works again
+    let g = gb
+        .insert_stop(0.75, Color::new(0.0, 0.0, 0.0, 1.0))
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(gb.get_positions(), &[0.0, 0.5, 0.75, 1.0]);
+    assert_eq!(g.at(0.75).to_css_hex(), "#000000");
+
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#00f"])
+        .domain(&[0.0, 1.0])
+        .insert_stop(f32::INFINITY, white);
+    assert!(gb.build::<LinearGradient>().is_err());
+    assert!(gb.css("#f00, 50%, #00f").build::<LinearGradient>().is_ok());
 }
@@ -1,4 +1,6 @@
-use colorgrad::{Gradient, GradientBuilder, LinearGradient};
+use colorgrad::{
+    BlendMode, Channel, Color, Gradient, GradientBuilder, LinearGradient, SharedGradient,
+};
 
 mod utils;
 use utils::*;
@@ -36,30 +38,30 @@ fn spread_repeat() {
         .build::<LinearGradient>()
         .unwrap();
 
-    assert_eq!(g.repeat_at(-2.0).to_hex_string(), "#000000");
-    assert_eq!(g.repeat_at(-1.9).to_hex_string(), "#1a1a1a");
-    assert_eq!(g.repeat_at(-1.5).to_hex_string(), "#808080");
-    assert_eq!(g.repeat_at(-1.1).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.repeat_at(-2.0).to_css_hex(), "#000000");
+    assert_eq!(g.repeat_at(-1.9).to_css_hex(), "#1a1a1a");
+    assert_eq!(g.repeat_at(-1.5).to_css_hex(), "#808080");
+    assert_eq!(g.repeat_at(-1.1).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.repeat_at(-1.0).to_hex_string(), "#000000");
-    assert_eq!(g.repeat_at(-0.9).to_hex_string(), "#1a1a1a");
-    assert_eq!(g.repeat_at(-0.5).to_hex_string(), "#808080");
-    assert_eq!(g.repeat_at(-0.1).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.repeat_at(-1.0).to_css_hex(), "#000000");
+    assert_eq!(g.repeat_at(-0.9).to_css_hex(), "#1a1a1a");
+    assert_eq!(g.repeat_at(-0.5).to_css_hex(), "#808080");
+    assert_eq!(g.repeat_at(-0.1).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.repeat_at(0.0).to_hex_string(), "#000000");
-    assert_eq!(g.repeat_at(0.1).to_hex_string(), "#1a1a1a");
-    assert_eq!(g.repeat_at(0.5).to_hex_string(), "#808080");
-    assert_eq!(g.repeat_at(0.9).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.repeat_at(0.0).to_css_hex(), "#000000");
+    assert_eq!(g.repeat_at(0.1).to_css_hex(), "#1a1a1a");
+    assert_eq!(g.repeat_at(0.5).to_css_hex(), "#808080");
+    assert_eq!(g.repeat_at(0.9).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.repeat_at(1.0).to_hex_string(), "#000000");
-    assert_eq!(g.repeat_at(1.1).to_hex_string(), "#1a1a1a");
-    assert_eq!(g.repeat_at(1.5).to_hex_string(), "#808080");
-    assert_eq!(g.repeat_at(1.9).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.repeat_at(1.0).to_css_hex(), "#000000");
+    assert_eq!(g.repeat_at(1.1).to_css_hex(), "#1a1a1a");
+    assert_eq!(g.repeat_at(1.5).to_css_hex(), "#808080");
+    assert_eq!(g.repeat_at(1.9).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.repeat_at(2.0).to_hex_string(), "#000000");
-    assert_eq!(g.repeat_at(2.1).to_hex_string(), "#191919");
-    assert_eq!(g.repeat_at(2.5).to_hex_string(), "#808080");
-    assert_eq!(g.repeat_at(2.9).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.repeat_at(2.0).to_css_hex(), "#000000");
+    assert_eq!(g.repeat_at(2.1).to_css_hex(), "#191919");
+    assert_eq!(g.repeat_at(2.5).to_css_hex(), "#808080");
+    assert_eq!(g.repeat_at(2.9).to_css_hex(), "#e6e6e6");
 }
 
 #[test]
@@ -69,30 +71,30 @@ fn spread_reflect() {
         .build::<LinearGradient>()
         .unwrap();
 
-    assert_eq!(g.reflect_at(-2.0).to_hex_string(), "#000000");
-    assert_eq!(g.reflect_at(-1.9).to_hex_string(), "#1a1a1a");
-    assert_eq!(g.reflect_at(-1.5).to_hex_string(), "#808080");
-    assert_eq!(g.reflect_at(-1.1).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.reflect_at(-2.0).to_css_hex(), "#000000");
+    assert_eq!(g.reflect_at(-1.9).to_css_hex(), "#1a1a1a");
+    assert_eq!(g.reflect_at(-1.5).to_css_hex(), "#808080");
+    assert_eq!(g.reflect_at(-1.1).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.reflect_at(-1.0).to_hex_string(), "#ffffff");
-    assert_eq!(g.reflect_at(-0.9).to_hex_string(), "#e6e6e6");
-    assert_eq!(g.reflect_at(-0.5).to_hex_string(), "#808080");
-    assert_eq!(g.reflect_at(-0.1).to_hex_string(), "#191919");
+    assert_eq!(g.reflect_at(-1.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.reflect_at(-0.9).to_css_hex(), "#e6e6e6");
+    assert_eq!(g.reflect_at(-0.5).to_css_hex(), "#808080");
+    assert_eq!(g.reflect_at(-0.1).to_css_hex(), "#191919");
 
-    assert_eq!(g.reflect_at(0.0).to_hex_string(), "#000000");
-    assert_eq!(g.reflect_at(0.1).to_hex_string(), "#191919");
-    assert_eq!(g.reflect_at(0.5).to_hex_string(), "#808080");
-    assert_eq!(g.reflect_at(0.9).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.reflect_at(0.0).to_css_hex(), "#000000");
+    assert_eq!(g.reflect_at(0.1).to_css_hex(), "#191919");
+    assert_eq!(g.reflect_at(0.5).to_css_hex(), "#808080");
+    assert_eq!(g.reflect_at(0.9).to_css_hex(), "#e6e6e6");
 
-    assert_eq!(g.reflect_at(1.0).to_hex_string(), "#ffffff");
-    assert_eq!(g.reflect_at(1.1).to_hex_string(), "#e6e6e6");
-    assert_eq!(g.reflect_at(1.5).to_hex_string(), "#808080");
-    assert_eq!(g.reflect_at(1.9).to_hex_string(), "#191919");
+    assert_eq!(g.reflect_at(1.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.reflect_at(1.1).to_css_hex(), "#e6e6e6");
+    assert_eq!(g.reflect_at(1.5).to_css_hex(), "#808080");
+    assert_eq!(g.reflect_at(1.9).to_css_hex(), "#191919");
 
-    assert_eq!(g.reflect_at(2.0).to_hex_string(), "#000000");
-    assert_eq!(g.reflect_at(2.1).to_hex_string(), "#191919");
-    assert_eq!(g.reflect_at(2.5).to_hex_string(), "#808080");
-    assert_eq!(g.reflect_at(2.9).to_hex_string(), "#e6e6e6");
+    assert_eq!(g.reflect_at(2.0).to_css_hex(), "#000000");
+    assert_eq!(g.reflect_at(2.1).to_css_hex(), "#191919");
+    assert_eq!(g.reflect_at(2.5).to_css_hex(), "#808080");
+    assert_eq!(g.reflect_at(2.9).to_css_hex(), "#e6e6e6");
 }
 
 #[test]
@@ -144,3 +146,1788 @@ fn box_clone() {
     let g: Box<dyn Gradient> = Box::new(GradientBuilder::new().build::<LinearGradient>().unwrap());
     let _: Box<dyn Gradient> = g.clone();
 }
+
+#[test]
+fn scale_alpha() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f08", "#00f"])
+        .domain(&[10.0, 20.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let ga = g.scale_alpha(0.5);
+    assert_eq!(ga.domain(), (10.0, 20.0));
+
+    for t in [5.0, 10.0, 12.5, 15.0, 17.3, 20.0, 25.0] {
+        let a = g.at(t);
+        let b = ga.at(t);
+        assert_eq!(b.a, a.a * 0.5);
+        assert_eq!((b.r, b.g, b.b), (a.r, a.g, a.b));
+    }
+
+    assert_eq!(g.scale_alpha(2.0).at(10.0).a, 1.0);
+    assert_eq!(g.scale_alpha(-1.0).at(10.0).a, 0.0);
+}
+
+#[test]
+fn shift_hue() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let gs = g.shift_hue(120.0);
+    assert_eq!(gs.domain(), (0.0, 1.0));
+
+    // red moves toward green
+    let c = gs.at(0.0);
+    assert!(c.g > c.r && c.g > c.b);
+
+    for t in [0.0, 0.3, 0.5, 1.0] {
+        let [l1, c1, h1, a1] = g.at(t).to_oklcha();
+        let [l2, c2, h2, a2] = gs.at(t).to_oklcha();
+        assert!((l1 - l2).abs() < 1e-3);
+        assert!((c1 - c2).abs() < 1e-3);
+        assert!(((h2 - h1).to_degrees().rem_euclid(360.0) - 120.0).abs() < 0.1);
+        assert_eq!(a1, a2);
+    }
+
+    let near = |a: Color, b: Color| {
+        let (a, b) = (a.to_array(), b.to_array());
+        (0..4).all(|i| (a[i] - b[i]).abs() < 1e-4)
+    };
+    assert!(near(g.shift_hue(0.0).at(0.7), g.at(0.7)));
+    assert!(near(g.shift_hue(360.0).at(0.7), g.at(0.7)));
+    assert!(near(g.shift_hue(-240.0).at(0.7), gs.at(0.7)));
+}
+
+#[test]
+fn clamp_lightness() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#f00", "#ff0", "#fff"])
+        .domain(&[0.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let gc = g.clamp_lightness(0.2, 0.9);
+    assert_eq!(gc.domain(), (0.0, 10.0));
+
+    for i in 0..=100 {
+        let t = i as f32 / 10.0;
+        let [l1, c1, h1, a1] = g.at(t).to_oklcha();
+        let [l2, c2, h2, a2] = gc.at(t).to_oklcha();
+        assert!((0.2 - 1e-4..=0.9 + 1e-4).contains(&l2));
+        if (0.2..=0.9).contains(&l1) {
+            assert!((l1 - l2).abs() < 1e-4);
+        }
+        assert!((c1 - c2).abs() < 1e-4);
+        if c1 > 0.01 {
+            assert!((h1 - h2).abs() < 1e-3);
+        }
+        assert_eq!(a1, a2);
+    }
+
+    // pure black and pure white are avoided
+    assert_eq!(gc.at(0.0).to_css_hex(), "#161616");
+    assert_eq!(gc.at(10.0).to_css_hex(), "#dedede");
+
+    // reversed range
+    assert_eq!(
+        g.clamp_lightness(0.9, 0.2).at(0.0).to_css_hex(),
+        gc.at(0.0).to_css_hex()
+    );
+}
+
+#[test]
+fn at_unit() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[10.0, 30.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.at_unit(0.5).to_array(), g.at(20.0).to_array());
+    assert_eq!(g.at_unit(0.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at_unit(0.5).to_css_hex(), "#00ff00");
+    assert_eq!(g.at_unit(1.0).to_css_hex(), "#0000ff");
+    assert_eq!(g.at_unit(0.25).to_array(), g.at(15.0).to_array());
+    assert_eq!(g.at_unit(-1.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at_unit(2.0).to_css_hex(), "#0000ff");
+    assert_eq!(g.at_unit(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn at_signed() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[10.0, 30.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.at_signed(-1.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at_signed(0.0).to_css_hex(), "#00ff00");
+    assert_eq!(g.at_signed(1.0).to_css_hex(), "#0000ff");
+    assert_eq!(g.at_signed(0.5).to_array(), g.at(25.0).to_array());
+    assert_eq!(g.at_signed(-2.0).to_css_hex(), "#ff0000");
+    assert_eq!(g.at_signed(2.0).to_css_hex(), "#0000ff");
+}
+
+#[test]
+fn at_index() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[10.0, 30.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.at_index(0, 4).to_array(), g.at(12.5).to_array());
+    assert_eq!(g.at_index(1, 4).to_array(), g.at(17.5).to_array());
+    assert_eq!(g.at_index(3, 4).to_array(), g.at(27.5).to_array());
+    assert_eq!(g.at_index(9, 4).to_array(), g.at(27.5).to_array());
+
+    assert_eq!(g.at_index(0, 1).to_css_hex(), "#00ff00");
+    assert_eq!(g.at_index(0, 0).to_css_hex(), "#00ff00");
+}
+
+#[test]
+fn is_cyclic() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f", "#f00"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(g.is_cyclic(colorgrad::CYCLE_TOLERANCE));
+    assert!(g.is_cyclic(1e-6));
+
+    // near cyclic
+    let g = GradientBuilder::new()
+        .html_colors(&["#ff0000", "#0f0", "#00f", "#fe0000"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(g.is_cyclic(colorgrad::CYCLE_TOLERANCE));
+    assert!(!g.is_cyclic(0.001));
+
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(!g.is_cyclic(colorgrad::CYCLE_TOLERANCE));
+}
+
+#[test]
+fn mse_against() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#ff0", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    for mode in [BlendMode::Rgb, BlendMode::LinearRgb, BlendMode::Oklab] {
+        assert!(g.mse_against(&g, mode, 100) < 1e-10);
+    }
+
+    // same colors in another domain
+    let g2 = GradientBuilder::new()
+        .html_colors(&["#f00", "#ff0", "#0f0", "#00f"])
+        .domain(&[-5.0, 5.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(g.mse_against(&g2, BlendMode::Oklab, 100) < 1e-10);
+
+    // shifted
+    let shifted = g.shift_hue(30.0);
+    let e1 = g.mse_against(&shifted, BlendMode::Rgb, 100);
+    assert!(e1 > 0.0);
+    let e2 = g.mse_against(&g.shift_hue(90.0), BlendMode::Rgb, 100);
+    assert!(e2 > e1);
+
+    // one channel off by 1 out of 4
+    let a = colorgrad::two_color(
+        Color::new(0.0, 0.0, 0.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 1.0),
+        BlendMode::Rgb,
+    );
+    let b = colorgrad::two_color(
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        BlendMode::Rgb,
+    );
+    assert!((a.mse_against(&b, BlendMode::Rgb, 10) - 0.25).abs() < 1e-6);
+
+    #[cfg(feature = "lab")]
+    {
+        let a = colorgrad::two_color(
+            Color::from_lcha(50.0, 40.0, 350_f32.to_radians(), 1.0),
+            Color::from_lcha(50.0, 40.0, 350_f32.to_radians(), 1.0),
+            BlendMode::Rgb,
+        );
+        let b = colorgrad::two_color(
+            Color::from_lcha(50.0, 40.0, 10_f32.to_radians(), 1.0),
+            Color::from_lcha(50.0, 40.0, 10_f32.to_radians(), 1.0),
+            BlendMode::Rgb,
+        );
+        // the hue difference is 20 degrees, not 340
+        let e = a.mse_against(&b, BlendMode::Lch, 10);
+        assert!(e > 0.0 && e < 20.0 * 20.0 / 4.0 + 1.0);
+    }
+}
+
+#[test]
+fn representative_colors() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let colors = g.representative_colors(0.01);
+    assert_eq!(colors2hex(&colors), &["#ff0000", "#0000ff"]);
+
+    // smooth gradient
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+    let smooth = g.representative_colors(0.01);
+    assert!(smooth.len() < 40);
+    assert_eq!(smooth[0].to_css_hex(), "#ff0000");
+    assert_eq!(smooth[smooth.len() - 1].to_css_hex(), "#0000ff");
+
+    // sharp gradient
+    let sharp = g.sharp(7, 0.0).representative_colors(0.01);
+    assert!(sharp.len() > smooth.len() * 2);
+
+    // a larger threshold gives fewer colors
+    assert!(g.representative_colors(0.05).len() < smooth.len());
+}
+
+#[test]
+fn max_contrast_pair() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    let (a, b, ratio) = g.max_contrast_pair(50);
+    assert_eq!((a, b), (-1.0, 1.0));
+    assert!((ratio - 21.0).abs() < 1e-3);
+
+    // the darkest and lightest colors are inside the domain
+    let g = GradientBuilder::new()
+        .html_colors(&["#777", "#fff", "#777", "#000", "#777"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let (a, b, ratio) = g.max_contrast_pair(5);
+    assert_eq!((a, b), (0.25, 0.75));
+    assert!((ratio - 21.0).abs() < 1e-3);
+
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#f00"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let (_, _, ratio) = g.max_contrast_pair(10);
+    assert!((ratio - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn jnd_color_count() {
+    let flat = GradientBuilder::new()
+        .html_colors(&["#808080"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(flat.jnd_color_count(1000), 1);
+
+    let two = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let n2 = two.jnd_color_count(1000);
+    assert!(n2 > 1);
+    // Oklab lightness of black to white is 0 to 1
+    assert!((40..=50).contains(&n2));
+
+    let rainbow = GradientBuilder::new()
+        .html_colors(&["#f00", "#ff0", "#0f0", "#0ff", "#00f", "#f0f", "#f00"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!(rainbow.jnd_color_count(1000) > n2);
+
+    assert_eq!(two.jnd_color_count(1), 1);
+    assert_eq!(two.jnd_color_count(0), 0);
+}
+
+#[test]
+fn distinct_rgba8_count() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#f00"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.distinct_rgba8_count(0), 0);
+    assert_eq!(g.distinct_rgba8_count(1), 1);
+    assert_eq!(g.distinct_rgba8_count(100), 100);
+
+    // only the red channel changes, at most 256 values
+    let n = g.distinct_rgba8_count(5000);
+    assert!(n > 200);
+    assert!(n <= 256);
+
+    let flat = GradientBuilder::new()
+        .html_colors(&["#808080"])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(flat.distinct_rgba8_count(1000), 1);
+}
+
+#[test]
+fn positions_for() {
+    let mut gb = GradientBuilder::new();
+    let g = gb
+        .html_colors(&["#f00", "#ff0", "#0f0", "#00f"])
+        .domain(&[0.0, 10.0, 60.0, 100.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let positions = g.positions_for(gb.get_colors(), 1001);
+    assert_eq!(positions.len(), 4);
+    for (t, stop) in positions.iter().zip(gb.get_positions()) {
+        assert!((t - stop).abs() < 0.1);
+    }
+
+    // between stops
+    let t = g.positions_for(&[g.at(35.0)], 1001)[0];
+    assert!((t - 35.0).abs() < 0.1);
+
+    // the alpha is ignored
+    let t = g.positions_for(&[Color::new(0.0, 0.0, 1.0, 0.5)], 1001)[0];
+    assert_eq!(t, 100.0);
+
+    assert!(g.positions_for(&[], 100).is_empty());
+    assert_eq!(
+        g.positions_for(&[Color::new(1.0, 0.0, 0.0, 1.0)], 0),
+        &[0.0]
+    );
+}
+
+#[test]
+fn perceptual_midpoint() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .domain(&[0.0, 100.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    // Oklab lightness 0.5 is sRGB 0.389
+    let t = g.perceptual_midpoint(0.0, 100.0, 1001);
+    assert!((t - 38.9).abs() < 0.5);
+    assert!((g.at(t).to_oklaba()[0] - 0.5).abs() < 0.005);
+
+    // sub-range
+    let t = g.perceptual_midpoint(50.0, 100.0, 501);
+    let l = g.at(t).to_oklaba()[0];
+    let (l0, l1) = (g.at(50.0).to_oklaba()[0], g.at(100.0).to_oklaba()[0]);
+    assert!((l - (l0 + l1) / 2.0).abs() < 0.005);
+    assert!(t > 50.0 && t < 75.0);
+
+    // perceptually uniform gradient
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!((g.perceptual_midpoint(0.0, 1.0, 101) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn first_crossing() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    // relative luminance 0.5 is sRGB 0.7354
+    let t = g.first_crossing(Channel::Luminance, 0.5, 10).unwrap();
+    assert!((t - 0.7354).abs() < 1e-3);
+    assert!((g.at(t).to_linear_rgba()[0] - 0.5).abs() < 1e-4);
+
+    let t = g.first_crossing(Channel::R, 0.25, 3).unwrap();
+    assert!((t - 0.25).abs() < 1e-5);
+
+    // exact sample
+    assert_eq!(g.first_crossing(Channel::G, 0.5, 3), Some(0.5));
+
+    // first of several crossings
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff", "#000", "#fff"])
+        .domain(&[0.0, 30.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    let t = g.first_crossing(Channel::B, 0.6, 50).unwrap();
+    assert!((t - 6.0).abs() < 1e-3);
+    let t = g.first_crossing(Channel::B, 0.6, 4).unwrap();
+    assert!((t - 6.0).abs() < 1e-3);
+
+    // never crossed
+    assert_eq!(g.first_crossing(Channel::A, 0.5, 50), None);
+    assert_eq!(g.first_crossing(Channel::R, 1.5, 50), None);
+}
+
+#[test]
+fn with_alpha_stops() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .domain(&[0.0, 100.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let a = g.with_alpha_stops(&[(80.0, 0.0), (20.0, 1.0)]);
+    assert_eq!(a.domain(), (0.0, 100.0));
+
+    let data = [
+        (0.0, 1.0),
+        (20.0, 1.0),
+        (35.0, 0.75),
+        (50.0, 0.5),
+        (65.0, 0.25),
+        (80.0, 0.0),
+        (100.0, 0.0),
+    ];
+    for (t, alpha) in data {
+        let c = a.at(t);
+        assert!((c.a - alpha).abs() < 1e-6);
+        assert_eq!(c.to_rgba8()[..3], g.at(t).to_rgba8()[..3]);
+    }
+
+    // NaN keeps the wrapped color
+    assert_eq!(a.at(f32::NAN), g.at(f32::NAN));
+    let a1 = g.with_alpha_stops(&[(50.0, 0.5)]);
+    assert_eq!(a1.at(f32::NAN), g.at(f32::NAN));
+
+    // multiplied into the color's alpha
+    let g = GradientBuilder::new()
+        .html_colors(&["#f008", "#00f8"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let a = g.with_alpha_stops(&[(0.0, 0.0), (1.0, 1.0)]);
+    assert!((a.at(0.5).a - g.at(0.5).a * 0.5).abs() < 1e-6);
+
+    // no stops
+    let a = g.with_alpha_stops(&[]);
+    assert_eq!(a.at(0.3), g.at(0.3));
+}
+
+#[test]
+fn channel() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#ff8000", "#ff800000"])
+        .domain(&[10.0, 20.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let alpha = g.channel(Channel::A);
+    assert_eq!(alpha.domain(), (10.0, 20.0));
+    assert_eq!(alpha.at(10.0).to_css_hex(), "#ffffff");
+    assert_eq!(alpha.at(15.0).to_css_hex(), "#808080");
+    assert_eq!(alpha.at(20.0).to_css_hex(), "#000000");
+
+    let mut prev = 1.1;
+    for c in alpha.colors(11) {
+        assert_eq!(c.r, c.g);
+        assert_eq!(c.r, c.b);
+        assert_eq!(c.a, 1.0);
+        assert!(c.r < prev);
+        prev = c.r;
+    }
+
+    assert_eq!(g.channel(Channel::R).at(12.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.channel(Channel::G).at(12.0).to_css_hex(), "#808080");
+    assert_eq!(g.channel(Channel::B).at(12.0).to_css_hex(), "#000000");
+
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#0f0", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let lum = g.channel(Channel::Luminance);
+    assert_eq!(lum.at(0.0).r, 0.0);
+    assert!((lum.at(0.5).r - 0.7152).abs() < 1e-4);
+    assert!((lum.at(1.0).r - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn mode() {
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(BlendMode::Oklab);
+
+    let g = gb.build::<LinearGradient>().unwrap();
+    assert_eq!(g.mode(), Some(BlendMode::Oklab));
+    assert_eq!(g.sharp(5, 0.2).mode(), Some(BlendMode::Oklab));
+    assert_eq!(g.clone().shared().mode(), Some(BlendMode::Oklab));
+    assert_eq!(g.smoothstepped(5).mode(), Some(BlendMode::Oklab));
+    assert_eq!(g.scale_alpha(0.5).mode(), None);
+    assert_eq!(
+        g.scale_alpha(0.5).smoothstepped(5).mode(),
+        Some(BlendMode::Rgb)
+    );
+
+    let g = gb.build::<colorgrad::BasisGradient>().unwrap();
+    assert_eq!(g.mode(), Some(BlendMode::Oklab));
+
+    let g = gb
+        .build_with_mode::<colorgrad::CatmullRomGradient>(BlendMode::LinearRgb)
+        .unwrap();
+    assert_eq!(g.mode(), Some(BlendMode::LinearRgb));
+
+    let boxed: Box<dyn Gradient> = Box::new(gb.build::<LinearGradient>().unwrap());
+    assert_eq!(boxed.mode(), Some(BlendMode::Oklab));
+
+    // channel modes override the blend mode
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .channel_modes([
+            colorgrad::ChannelMode::Circular,
+            colorgrad::ChannelMode::Linear,
+            colorgrad::ChannelMode::Linear,
+        ])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.mode(), None);
+}
+
+#[test]
+fn crossfade() {
+    let a = GradientBuilder::new()
+        .html_colors(&["#f00", "#ff0", "#0f0"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let b = GradientBuilder::new()
+        .html_colors(&["#00f", "#000"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let frames = colorgrad::crossfade(&a, &b, 5, BlendMode::Rgb);
+    assert_eq!(frames.len(), 5);
+
+    for t in &[0.0, 0.1, 0.25, 0.5, 0.8, 1.0] {
+        let u = -10.0 + t * 20.0;
+        // first frame matches a, last frame matches b
+        assert_eq!(frames[0].at(*t).to_rgba8(), a.at(*t).to_rgba8());
+        assert_eq!(frames[4].at(*t).to_rgba8(), b.at(u).to_rgba8());
+
+        // middle frame is the blend
+        let x = a.at(*t).interpolate_rgb(&b.at(u), 0.5).to_array();
+        let y = frames[2].at(*t).to_array();
+        for (v, e) in y.iter().zip(x) {
+            assert!((v - e).abs() < 0.005);
+        }
+    }
+
+    for f in &frames {
+        assert_eq!(f.domain(), (0.0, 1.0));
+    }
+
+    assert_eq!(colorgrad::crossfade(&a, &b, 1, BlendMode::Oklab).len(), 1);
+    assert!(colorgrad::crossfade(&a, &b, 0, BlendMode::Oklab).is_empty());
+}
+
+#[test]
+fn hex_field() {
+    let gx = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .domain(&[-2.0, 2.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    let gy = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .domain(&[-2.0, 2.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    let field = colorgrad::hex_field(&gx, &gy);
+
+    let expected = |x: f32, y: f32| gx.at(x).interpolate_rgb(&gy.at(y), 0.5).to_rgba8();
+    let h = 3f32.sqrt() / 2.0;
+
+    assert_eq!(field(0, 0).to_rgba8(), expected(0.0, 0.0));
+    assert_eq!(field(0, 0).to_rgba8(), [128, 64, 128, 255]);
+
+    // the six neighbors of the origin
+    assert_eq!(field(1, 0).to_rgba8(), expected(1.0, 0.0));
+    assert_eq!(field(-1, 0).to_rgba8(), expected(-1.0, 0.0));
+    assert_eq!(field(0, 1).to_rgba8(), expected(0.5, h));
+    assert_eq!(field(0, -1).to_rgba8(), expected(-0.5, -h));
+    assert_eq!(field(1, -1).to_rgba8(), expected(0.5, -h));
+    assert_eq!(field(-1, 1).to_rgba8(), expected(-0.5, h));
+
+    // green only comes from gx, blue minus green only from gy
+    let (a, b, c) = (field(0, 0), field(1, 0), field(0, 1));
+    assert!(b.g > a.g);
+    assert!(((b.b - b.g) - (a.b - a.g)).abs() < 1e-6);
+    assert!(c.b - c.g > a.b - a.g);
+}
+
+#[test]
+fn nice_ticks() {
+    let ticks = |domain: &[f32], n: usize| {
+        GradientBuilder::new()
+            .html_colors(&["#f00", "#00f"])
+            .domain(domain)
+            .build::<LinearGradient>()
+            .unwrap()
+            .nice_ticks(n)
+            .iter()
+            .map(|(t, _)| *t)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ticks(&[0.0, 97.0], 5), &[0.0, 20.0, 40.0, 60.0, 80.0]);
+    assert_eq!(ticks(&[0.0, 1.0], 5), &[0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+    assert_eq!(ticks(&[0.0, 1.0], 10).len(), 11);
+    assert_eq!(ticks(&[-1.0, 1.0], 4), &[-1.0, -0.5, 0.0, 0.5, 1.0]);
+    assert_eq!(ticks(&[3.0, 1234.0], 3), &[500.0, 1000.0]);
+    assert_eq!(ticks(&[0.13, 0.17], 4), &[0.13, 0.14, 0.15, 0.16, 0.17]);
+    assert!(ticks(&[0.0, 1.0], 0).is_empty());
+
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .domain(&[0.0, 97.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    for (t, c) in g.nice_ticks(5) {
+        assert_eq!(c, g.at(t));
+    }
+}
+
+#[test]
+fn data_ticks() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    // uniform data in [0, 100], unsorted
+    let data = (0..=100).rev().map(|x| x as f32).collect::<Vec<_>>();
+    let ticks = g.data_ticks(&data, 5);
+    assert_eq!(ticks.len(), 5);
+
+    for (i, (v, c)) in ticks.iter().enumerate() {
+        assert!((v - i as f32 * 25.0).abs() < 1e-4);
+        assert_eq!(c.to_rgba8(), g.at(-1.0 + i as f32 * 0.5).to_rgba8());
+    }
+
+    // skewed data
+    let data = [0.0, 1.0, 1.0, 2.0, f32::NAN, 100.0];
+    let ticks = g.data_ticks(&data, 3);
+    assert_eq!(ticks[0].0, 0.0);
+    assert_eq!(ticks[1].0, 1.0);
+    assert_eq!(ticks[2].0, 100.0);
+    assert_eq!(ticks[0].1.to_css_hex(), "#ff0000");
+    assert_eq!(ticks[1].1.to_rgba8(), g.at(-0.98).to_rgba8());
+    assert_eq!(ticks[2].1.to_css_hex(), "#0000ff");
+
+    assert!(g.data_ticks(&[], 5).is_empty());
+    assert!(g.data_ticks(&[1.0, 2.0], 0).is_empty());
+    assert_eq!(g.data_ticks(&[7.0, 7.0], 2)[1].0, 7.0);
+}
+
+#[test]
+fn blend_many() {
+    let solid = |c: Color, domain: &[f32]| {
+        GradientBuilder::new()
+            .colors(&[c])
+            .domain(domain)
+            .build::<LinearGradient>()
+            .unwrap()
+    };
+    let a = solid(Color::new(0.9, 0.0, 0.3, 1.0), &[0.0, 1.0]);
+    let b = solid(Color::new(0.0, 0.6, 0.3, 0.4), &[-5.0, 5.0]);
+    let c = solid(Color::new(0.3, 0.3, 0.6, 0.1), &[10.0, 20.0]);
+
+    let g = colorgrad::blend_many(&[(&a, 1.0), (&b, 1.0), (&c, 1.0)], BlendMode::Rgb);
+    assert_eq!(g.domain(), (0.0, 1.0));
+
+    for t in [-1.0, 0.0, 0.5, 1.0, 2.0] {
+        let col = g.at(t).to_array();
+        for (v, e) in col.iter().zip([0.4, 0.3, 0.4, 0.5]) {
+            assert!((v - e).abs() < 1e-6);
+        }
+    }
+
+    // weights are normalized, negative weights are ignored
+    let g = colorgrad::blend_many(&[(&a, 6.0), (&b, 2.0), (&c, -1.0)], BlendMode::Rgb);
+    let col = g.at(0.5).to_array();
+    for (v, e) in col.iter().zip([0.675, 0.15, 0.3, 0.85]) {
+        assert!((v - e).abs() < 1e-6);
+    }
+
+    // sampled at the same relative position
+    let x = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let y = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    let g = colorgrad::blend_many(&[(&y, 1.0), (&x, 0.0)], BlendMode::Oklab);
+    assert_eq!(g.domain(), (-10.0, 10.0));
+    assert_eq!(g.at(5.0).to_css_hex(), x.at(0.75).to_css_hex());
+
+    // zero total weight is an equal blend
+    let g = colorgrad::blend_many(&[(&a, 0.0), (&a, 0.0)], BlendMode::Rgb);
+    assert_eq!(g.at(0.5).to_css_hex(), a.at(0.5).to_css_hex());
+}
+
+#[test]
+fn effects() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 3.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    // same as nested wrappers
+    let composed = g.effects().shift_hue(45.0).scale_alpha(0.5);
+    let nested = g.shift_hue(45.0).scale_alpha(0.5);
+    assert_eq!(composed.domain(), (-1.0, 3.0));
+
+    for t in [-2.0, -1.0, 0.0, 0.4, 1.0, 2.5, 3.0, 4.0] {
+        assert_eq!(composed.at(t).to_array(), nested.at(t).to_array());
+    }
+
+    // position effects
+    let inv = g.effects().invert();
+    let gam = g.effects().gamma(2.0);
+    let warp = g.effects().warp(|t| 1.0 - t);
+
+    for t in [-1.0, -0.5, 0.0, 1.0, 2.2, 3.0] {
+        let u = (t + 1.0) / 4.0;
+        assert_eq!(inv.at(t).to_rgba8(), g.at(2.0 - t).to_rgba8());
+        assert_eq!(gam.at(t).to_rgba8(), g.at(-1.0 + u * u * 4.0).to_rgba8());
+        assert_eq!(warp.at(t).to_rgba8(), inv.at(t).to_rgba8());
+    }
+
+    // the last position effect remaps the position first, like nested wrappers
+    let a = g.effects().gamma(2.0).invert();
+    let b = g.effects().invert().gamma(2.0);
+    assert_eq!(a.at(-1.0).to_css_hex(), "#0000ff");
+    assert_eq!(b.at(-1.0).to_css_hex(), "#0000ff");
+    assert_eq!(
+        a.at(0.0).to_rgba8(),
+        g.at(-1.0 + 0.75 * 0.75 * 4.0).to_rgba8()
+    );
+    assert_eq!(
+        b.at(0.0).to_rgba8(),
+        g.at(2.0 - (-1.0 + 0.0625 * 4.0)).to_rgba8()
+    );
+
+    // color effects
+    let g2 = GradientBuilder::new()
+        .colors(&[
+            Color::new(1.5, -0.5, 0.5, 1.0),
+            Color::new(0.0, 0.0, 1.0, 1.0),
+        ])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(
+        g2.effects().clamp().at(0.0).to_array(),
+        [1.0, 0.0, 0.5, 1.0]
+    );
+    assert_eq!(g2.effects().scale_alpha(0.25).at(1.0).a, 0.25);
+    assert_eq!(
+        g2.effects().scale_alpha(0.5).scale_alpha(0.5).at(1.0).a,
+        0.25
+    );
+
+    // double invert is unwrapped
+    let g3 = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.1, 0.7])
+        .build::<LinearGradient>()
+        .unwrap();
+    let twice = g3.effects().invert().invert();
+    assert_eq!(format!("{:?}", twice), format!("{:?}", g3.effects()));
+    for t in [0.1, 0.3, 0.33, 0.5, 0.7] {
+        assert_eq!(twice.at(t).to_array(), g3.at(t).to_array());
+    }
+    let thrice = g3.effects().invert().invert().invert();
+    assert_eq!(
+        thrice.at(0.3).to_array(),
+        g3.effects().invert().at(0.3).to_array()
+    );
+
+    let boxed: Box<dyn Gradient> = Box::new(g.effects().invert().clamp());
+    assert_eq!(boxed.at(-1.0).to_css_hex(), "#0000ff");
+}
+
+#[test]
+fn at_p3() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#fff", "#00ff0080", "#000"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let near = |a: [f32; 4], b: [f32; 4]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-3);
+
+    // sRGB red is inside the P3 gamut
+    assert!(near(g.at_p3(0.0), [0.9175, 0.2003, 0.1386, 1.0]));
+    // same white point
+    assert!(near(g.at_p3(1.0 / 3.0), [1.0, 1.0, 1.0, 1.0]));
+    assert!(near(
+        g.at_p3(2.0 / 3.0),
+        [0.4584, 0.9853, 0.2983, 128.0 / 255.0]
+    ));
+    assert!(near(g.at_p3(1.0), [0.0, 0.0, 0.0, 1.0]));
+}
+
+#[test]
+fn to_linear_baked() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let baked = g.to_linear_baked(5);
+    assert_eq!(baked.domain(), (-1.0, 1.0));
+
+    for t in [-1.0, -0.5, 0.0, 0.5, 1.0] {
+        let a = baked.at(t).to_array();
+        let b = g.at(t).to_linear_rgba();
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    // between the samples, the interpolation is in linear light
+    let lrgb = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(BlendMode::LinearRgb)
+        .build::<LinearGradient>()
+        .unwrap();
+    let baked = lrgb.to_linear_baked(3);
+    for t in [0.1, 0.25, 0.6, 0.9] {
+        let a = baked.at(t).to_array();
+        let b = lrgb.at(t).to_linear_rgba();
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+    assert_ne!(
+        baked.at(0.25).to_rgba8(),
+        lrgb.at(0.25).to_rgba8(),
+        "the baked colors are not sRGB encoded"
+    );
+}
+
+#[test]
+fn to_wgsl_array() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.to_wgsl_array("MY_MAP", 9);
+    assert!(s.starts_with("const MY_MAP: array<vec4<f32>, 9> = array<vec4<f32>, 9>(\n"));
+    assert!(s.contains("fn sample_my_map(t: f32) -> vec4<f32> {"));
+    assert!(s.contains("var colors = MY_MAP;"));
+    assert!(s.contains("let j = min(i + 1u, 8u);"));
+
+    let vecs = s
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("vec4<f32>("))
+        .map(|l| l.strip_suffix("),").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vecs.len(), 9);
+
+    for (v, c) in vecs.iter().zip(g.colors(9)) {
+        let v = v
+            .split(", ")
+            .map(|x| {
+                assert!(x.contains('.'));
+                x.parse::<f32>().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(v, c.to_linear_rgba());
+    }
+    assert!(s.contains("vec4<f32>(0.0, 0.0, 1.0, 1.0),\n);"));
+
+    assert!(g.to_wgsl_array("A", 0).contains("array<vec4<f32>, 2>"));
+}
+
+#[test]
+fn to_csv() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let csv = g.to_csv(11);
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "t,r,g,b,a,hex,L,C,h");
+
+    for (line, c) in lines[1..].iter().zip(g.colors(11)) {
+        let cols = line.split(',').collect::<Vec<_>>();
+        assert_eq!(cols.len(), 9);
+        assert_eq!(cols[5], c.to_css_hex());
+        assert_eq!(Color::from_html(cols[5]).unwrap().to_rgba8(), c.to_rgba8());
+        for (i, v) in cols.iter().enumerate() {
+            if i != 5 {
+                assert!(v.parse::<f32>().is_ok());
+            }
+        }
+    }
+
+    assert!(lines[1].starts_with("-1,1,0,0,1,#ff0000,"));
+    assert!(lines[11].starts_with("1,0,0,1,1,#0000ff,"));
+
+    let h = lines[6].split(',').nth(8).unwrap().parse::<f32>().unwrap();
+    assert!((h - g.lch_profile(3)[1][2]).abs() < 1e-3);
+}
+
+#[test]
+fn to_css_repeating() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        g.to_css_repeating(3, 20.0),
+        "repeating-linear-gradient(#ff0000 0%, #00ff00 10%, #0000ff 20%)"
+    );
+
+    let s = g.to_css_repeating(7, 25.0);
+    assert!(s.starts_with("repeating-linear-gradient("));
+    let stops = s
+        .strip_prefix("repeating-linear-gradient(")
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap()
+        .split(", ")
+        .collect::<Vec<_>>();
+    assert_eq!(stops.len(), 7);
+
+    for (stop, c) in stops.iter().zip(g.colors(7)) {
+        let (hex, pos) = stop.split_once(' ').unwrap();
+        assert_eq!(hex, c.to_css_hex());
+        let pos = pos.strip_suffix('%').unwrap().parse::<f32>().unwrap();
+        assert!((0.0..=25.0).contains(&pos));
+    }
+    assert!(stops[6].ends_with(" 25%"));
+
+    // the stops can be parsed back
+    let g2 = GradientBuilder::new()
+        .css(&stops.join(", "))
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g2.at(0.25).to_css_hex(), "#0000ff");
+}
+
+#[test]
+fn to_gnuplot() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        g.to_gnuplot(3),
+        "set palette defined (0 1 0 0, 0.5 0 1 0, 1 0 0 1)"
+    );
+
+    let s = g.to_gnuplot(10);
+    let entries = s
+        .strip_prefix("set palette defined (")
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap()
+        .split(", ")
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 10);
+
+    for e in entries {
+        let v = e
+            .split(' ')
+            .map(|x| x.parse::<f32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(v.len(), 4);
+        assert!(v.iter().all(|x| (0.0..=1.0).contains(x)));
+    }
+}
+
+#[test]
+fn sample_aa_into() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff", "#000", "#fff"])
+        .domain(&[0.0, 100.0])
+        .build::<LinearGradient>()
+        .unwrap()
+        .sharp(4, 0.0);
+
+    let max_step = |colors: &[Color]| {
+        colors
+            .windows(2)
+            .map(|w| (w[0].r - w[1].r).abs())
+            .fold(0.0, f32::max)
+    };
+
+    let mut plain = vec![Color::default(); 50];
+    let mut aa = vec![Color::default(); 50];
+    g.sample_aa_into(&mut plain, 0.0);
+    g.sample_aa_into(&mut aa, 100.0 / 50.0);
+
+    assert_eq!(colors2hex(&plain), colors2hex(&g.colors(50)));
+    assert_eq!(max_step(&plain), 1.0);
+    assert!(max_step(&aa) < 1.0);
+    assert!(aa.iter().any(|c| c.r > 0.0 && c.r < 1.0));
+
+    // flat regions are unchanged
+    assert_eq!(aa[0].to_css_hex(), "#000000");
+    assert_eq!(aa[6].to_css_hex(), "#000000");
+    assert_eq!(aa[49].to_css_hex(), "#ffffff");
+
+    let mut empty: Vec<Color> = Vec::new();
+    g.sample_aa_into(&mut empty, 1.0);
+}
+
+#[test]
+fn to_pgfplots_colormap() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        g.to_pgfplots_colormap("rgb", 3),
+        "\\pgfplotsset{colormap={rgb}{rgb255=(255,0,0) rgb255=(0,255,0) rgb255=(0,0,255)}}"
+    );
+
+    let s = g.to_pgfplots_colormap("my map", 10);
+    let entries = s
+        .strip_prefix("\\pgfplotsset{colormap={my map}{")
+        .and_then(|s| s.strip_suffix("}}"))
+        .unwrap()
+        .split(' ')
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 10);
+
+    for e in entries {
+        let v = e
+            .strip_prefix("rgb255=(")
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap()
+            .split(',')
+            .map(|x| x.parse::<u8>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(v.len(), 3);
+    }
+}
+
+#[test]
+fn preview_rgba8() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    for max_bytes in [0, 3, 4, 7, 8, 100, 1023, 1024] {
+        let (width, buf) = g.preview_rgba8(max_bytes);
+        assert_eq!(buf.len(), width as usize * 4);
+        assert!(buf.len() <= max_bytes);
+        assert!(buf.len() + 4 > max_bytes);
+    }
+
+    let (width, buf) = g.preview_rgba8(12);
+    assert_eq!(width, 3);
+    assert_eq!(buf, [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255]);
+}
+
+#[test]
+fn to_ansi_bar_256() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f", "#808080", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.to_ansi_bar_256(30);
+    let s = s.strip_suffix("\x1b[0m").unwrap();
+    let cells = s
+        .split('\u{2588}')
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    assert_eq!(cells.len(), 30);
+    assert_eq!(s.matches('\u{2588}').count(), 30);
+
+    let codes = cells
+        .iter()
+        .map(|c| {
+            c.strip_prefix("\x1b[38;5;")
+                .and_then(|c| c.strip_suffix('m'))
+                .unwrap()
+                .parse::<u8>()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(codes.iter().all(|c| *c >= 16));
+
+    let bar = |hex: &str| {
+        GradientBuilder::new()
+            .html_colors(&[hex])
+            .build::<LinearGradient>()
+            .unwrap()
+            .to_ansi_bar_256(1)
+    };
+    assert_eq!(bar("#f00"), "\x1b[38;5;196m\u{2588}\x1b[0m");
+    assert_eq!(bar("#00f"), "\x1b[38;5;21m\u{2588}\x1b[0m");
+    assert_eq!(bar("#000"), "\x1b[38;5;16m\u{2588}\x1b[0m");
+    assert_eq!(bar("#fff"), "\x1b[38;5;231m\u{2588}\x1b[0m");
+    assert_eq!(bar("#808080"), "\x1b[38;5;244m\u{2588}\x1b[0m");
+    assert_eq!(bar("#5f87af"), "\x1b[38;5;67m\u{2588}\x1b[0m");
+
+    assert_eq!(g.to_ansi_bar_256(0), "");
+}
+
+#[test]
+fn color_signature() {
+    let build = |colors: &[&str]| {
+        GradientBuilder::new()
+            .html_colors(colors)
+            .build::<LinearGradient>()
+            .unwrap()
+    };
+    let a = build(&["#f00", "#0f0", "#00f"]);
+    let b = build(&["#f00", "#0f0", "#00f"]);
+    let c = build(&["#f00", "#0f1", "#00f"]);
+
+    let sig = a.color_signature(64);
+    assert_eq!(sig.len(), 16);
+    assert!(sig.chars().all(|c| c.is_ascii_hexdigit()));
+
+    assert_eq!(sig, b.color_signature(64));
+    assert_ne!(sig, c.color_signature(64));
+    assert_ne!(sig, a.color_signature(65));
+
+    // FNV-1a offset basis
+    assert_eq!(a.color_signature(0), "cbf29ce484222325");
+}
+
+#[test]
+fn to_matplotlib() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.to_matplotlib("my_map", 7);
+    assert!(s.starts_with("from matplotlib.colors import ListedColormap\n"));
+    assert!(s.contains("name=\"my_map\""));
+
+    let list = s
+        .split_once("ListedColormap([")
+        .and_then(|(_, s)| s.split_once(']'))
+        .unwrap()
+        .0;
+    let tuples = list
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap()
+        .split("), (")
+        .collect::<Vec<_>>();
+    assert_eq!(tuples.len(), 7);
+
+    for t in tuples {
+        let v = t
+            .split(", ")
+            .map(|x| x.parse::<f32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(v.len(), 3);
+        assert!(v.iter().all(|x| (0.0..=1.0).contains(x)));
+    }
+
+    assert!(g.to_matplotlib("a\"b", 2).contains("name=\"a\\\"b\""));
+}
+
+#[test]
+fn to_flutter() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        g.to_flutter(3),
+        "LinearGradient(colors: [Color(0xFFFF0000), Color(0xFF00FF00), Color(0xFF0000FF)], \
+        stops: [0.0, 0.5, 1.0])"
+    );
+
+    let s = g.to_flutter(9);
+    let (colors, stops) = s
+        .strip_prefix("LinearGradient(colors: [")
+        .and_then(|s| s.strip_suffix("])"))
+        .and_then(|s| s.split_once("], stops: ["))
+        .unwrap();
+    let colors = colors.split(", ").collect::<Vec<_>>();
+    let stops = stops
+        .split(", ")
+        .map(|x| x.parse::<f32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(colors.len(), 9);
+    assert_eq!(stops.len(), 9);
+
+    for ((c, t), col) in colors.iter().zip(&stops).zip(g.colors(9)) {
+        let [r, g, b, a] = col.to_rgba8();
+        assert_eq!(c, &format!("Color(0x{:02X}{:02X}{:02X}{:02X})", a, r, g, b));
+        assert!((0.0..=1.0).contains(t));
+    }
+    assert_eq!(stops[0], 0.0);
+    assert_eq!(stops[8], 1.0);
+}
+
+#[test]
+fn export_stops_normalized() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let stops = g.export_stops_normalized(5);
+    assert_eq!(stops.len(), 5);
+    assert_eq!(stops[0], (0.0, [255, 0, 0, 255]));
+    assert_eq!(stops[2], (0.5, [0, 255, 0, 255]));
+    assert_eq!(stops[4], (1.0, [0, 0, 255, 255]));
+
+    for (i, (t, c)) in stops.iter().enumerate() {
+        assert_eq!(*t, i as f32 / 4.0);
+        assert_eq!(*c, g.at(-10.0 + t * 20.0).to_rgba8());
+    }
+
+    assert_eq!(g.export_stops_normalized(1), vec![(0.0, [255, 0, 0, 255])]);
+    assert!(g.export_stops_normalized(0).is_empty());
+}
+
+#[test]
+fn qt_stops() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let stops = g.qt_stops(5);
+    assert_eq!(stops.len(), 5);
+    assert_eq!(stops[0], (0.0, [255, 0, 0, 255]));
+    assert_eq!(stops[2], (0.5, [0, 255, 0, 255]));
+    assert_eq!(stops[4], (1.0, [0, 0, 255, 255]));
+
+    for w in g.qt_stops(50).windows(2) {
+        assert!((0.0..=1.0).contains(&w[0].0));
+        assert!(w[0].0 < w[1].0);
+    }
+
+    assert!(g.qt_stops(0).is_empty());
+}
+
+#[test]
+fn to_svg_linear() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f8"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.to_svg_linear("a\"b", 5);
+    let inner = s
+        .strip_prefix("<linearGradient id=\"a&quot;b\">")
+        .and_then(|s| s.strip_suffix("</linearGradient>"))
+        .unwrap();
+
+    let stops = inner
+        .split_terminator("/>")
+        .map(|e| e.strip_prefix("<stop ").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(stops.len(), 5);
+
+    for (i, stop) in stops.iter().enumerate() {
+        let attrs = stop
+            .split(' ')
+            .map(|a| {
+                let (k, v) = a.split_once('=').unwrap();
+                (
+                    k,
+                    v.strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(attrs[0], ("offset", format!("{}%", i * 25).as_str()));
+        assert_eq!(attrs[1].0, "stop-color");
+        // only translucent colors have stop-opacity
+        assert_eq!(attrs.len(), if i < 3 { 2 } else { 3 });
+    }
+
+    assert!(stops[0].contains("stop-color=\"#ff0000\""));
+    assert!(stops[2].contains("stop-color=\"#00ff00\""));
+    assert!(stops[4].contains("stop-color=\"#0000ff\" stop-opacity=\"0.53333336\""));
+
+    assert_eq!(
+        g.to_svg_linear("x", 0),
+        "<linearGradient id=\"x\"></linearGradient>"
+    );
+}
+
+#[test]
+fn interior_colors() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    assert_eq!(g.interior_colors(0).len(), 0);
+    assert_eq!(colors2hex(&g.interior_colors(1)), &["#00ff00"]);
+    assert_eq!(
+        colors2hex(&g.interior_colors(3)),
+        &["#808000", "#00ff00", "#008080"]
+    );
+
+    // no sample at the domain min or max
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .domain(&[-10.0, 10.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let colors = g.interior_colors(9);
+    assert_eq!(colors.len(), 9);
+    for c in &colors {
+        assert_ne!(c.to_rgba8(), g.at(-10.0).to_rgba8());
+        assert_ne!(c.to_rgba8(), g.at(10.0).to_rgba8());
+    }
+    assert_eq!(colors[4].to_css_hex(), "#808080");
+}
+
+#[test]
+fn to_dual_texture() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f08", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let (srgb, linear) = g.to_dual_texture(7);
+    assert_eq!(srgb.len(), 7 * 4);
+    assert_eq!(linear.len(), 7 * 4);
+
+    for (i, c) in g.colors(7).iter().enumerate() {
+        assert_eq!(&srgb[i * 4..i * 4 + 4], &c.to_rgba8());
+        let lin = c.to_linear_rgba().map(|v| (v * 255.0).round() as u8);
+        assert_eq!(&linear[i * 4..i * 4 + 4], &lin);
+    }
+
+    assert_eq!(&srgb[12..16], &[0, 255, 0, 136]);
+    assert_eq!(&linear[4..8], &[103, 23, 0, 215]);
+
+    let (srgb, linear) = g.to_dual_texture(0);
+    assert!(srgb.is_empty() && linear.is_empty());
+}
+
+#[test]
+fn to_napari() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f08", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let v: serde_json::Value = serde_json::from_str(&g.to_napari("my map", 7)).unwrap();
+    let obj = v.as_object().unwrap();
+    assert_eq!(obj.len(), 1);
+
+    let rows = obj["my map"].as_array().unwrap();
+    assert_eq!(rows.len(), 7);
+    for row in rows {
+        let row = row.as_array().unwrap();
+        assert_eq!(row.len(), 4);
+        assert!(row
+            .iter()
+            .all(|x| (0.0..=1.0).contains(&x.as_f64().unwrap())));
+    }
+
+    assert_eq!(rows[0], serde_json::json!([1, 0, 0, 1]));
+    assert_eq!(rows[3][1], 1);
+    assert!((rows[3][3].as_f64().unwrap() - 0.533).abs() < 0.001);
+    assert_eq!(rows[6], serde_json::json!([0, 0, 1, 1]));
+
+    // name is escaped
+    let v: serde_json::Value = serde_json::from_str(&g.to_napari(r#"a "b" \c"#, 2)).unwrap();
+    assert_eq!(v[r#"a "b" \c"#].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn to_lottie_ramp() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f08", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let v: serde_json::Value = serde_json::from_str(&g.to_lottie_ramp(5)).unwrap();
+    assert_eq!(v["p"], 5);
+    assert_eq!(v["k"]["a"], 0);
+
+    let k = v["k"]["k"].as_array().unwrap();
+    // 5 color stops with 4 values, 5 opacity stops with 2 values
+    assert_eq!(k.len(), 5 * 4 + 5 * 2);
+    assert!(k.iter().all(|x| (0.0..=1.0).contains(&x.as_f64().unwrap())));
+
+    assert_eq!(k[8], 0.5);
+    assert_eq!(&k[9..12], &[0.0, 1.0, 0.0]);
+    assert_eq!(k[20], 0.0);
+    assert_eq!(k[21], 1.0);
+    assert_eq!(k[24], 0.5);
+    assert!((k[25].as_f64().unwrap() - 0.533).abs() < 0.001);
+}
+
+#[test]
+fn with_bounds() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let gb = g.with_bounds(
+        Color::new(0.0, 0.0, 0.0, 1.0),
+        Color::new(1.0, 1.0, 1.0, 1.0),
+    );
+    assert_eq!(gb.domain(), (-1.0, 1.0));
+
+    assert_eq!(gb.at(-1.5).to_css_hex(), "#000000");
+    assert_eq!(gb.at(-1.001).to_css_hex(), "#000000");
+    assert_eq!(gb.at(1.001).to_css_hex(), "#ffffff");
+    assert_eq!(gb.at(100.0).to_css_hex(), "#ffffff");
+
+    for t in [-1.0, -0.5, 0.0, 0.3, 1.0] {
+        assert_eq!(gb.at(t).to_rgba8(), g.at(t).to_rgba8());
+    }
+
+    assert_eq!(
+        colors2hex(&gb.colors(3)),
+        &["#ff0000", "#00ff00", "#0000ff"]
+    );
+}
+
+#[test]
+fn with_nan_color() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let gn = g.with_nan_color(Color::new(0.0, 0.0, 0.0, 0.0));
+    assert_eq!(gn.domain(), (-1.0, 1.0));
+    assert_eq!(gn.at(f32::NAN).to_array(), [0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(g.at(f32::NAN).to_css_hex(), "#000000");
+
+    for t in [
+        -2.0,
+        -1.0,
+        -0.5,
+        0.0,
+        0.3,
+        1.0,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+    ] {
+        assert_eq!(gn.at(t).to_array(), g.at(t).to_array());
+    }
+
+    let gn = g
+        .with_bounds(
+            Color::new(0.0, 0.0, 0.0, 1.0),
+            Color::new(1.0, 1.0, 1.0, 1.0),
+        )
+        .with_nan_color(Color::new(1.0, 0.0, 1.0, 1.0));
+    assert_eq!(gn.at(f32::NAN).to_css_hex(), "#ff00ff");
+    assert_eq!(gn.at(5.0).to_css_hex(), "#ffffff");
+}
+
+#[test]
+fn shared() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let gs = g.clone().shared();
+    let gs2 = gs.clone();
+    assert!(SharedGradient::ptr_eq(&gs, &gs2));
+    assert!(!SharedGradient::ptr_eq(&gs, &g.clone().shared()));
+
+    assert_eq!(gs.domain(), (-1.0, 1.0));
+    for t in [-2.0, -1.0, -0.3, 0.0, 0.5, 1.0, 2.0] {
+        assert_eq!(gs.at(t).to_rgba8(), g.at(t).to_rgba8());
+        assert_eq!(gs2.at(t).to_rgba8(), g.at(t).to_rgba8());
+    }
+
+    let handle = std::thread::spawn(move || gs2.at(0.0).to_css_hex());
+    assert_eq!(handle.join().unwrap(), "#00ff00");
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn to_repeating_image() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[10.0, 20.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let img = g.to_repeating_image(200, 10, 2.0);
+    assert_eq!(img.dimensions(), (200, 10));
+
+    assert_eq!(img.get_pixel(50, 0), img.get_pixel(150, 9));
+    assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(100, 0).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(50, 5).0, g.at(15.0).to_rgba8());
+    assert_eq!(img.get_pixel(20, 3), img.get_pixel(120, 7));
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn write_indexed_png() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let decode = |buf: &[u8]| {
+        let mut reader = png::Decoder::new(std::io::Cursor::new(buf.to_vec()))
+            .read_info()
+            .unwrap();
+        let mut data = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut data).unwrap();
+        let info = reader.info();
+        (
+            info.width,
+            info.color_type,
+            info.palette.as_ref().unwrap().to_vec(),
+            info.trns.as_ref().map(|t| t.to_vec()),
+            data,
+        )
+    };
+
+    let mut buf = Vec::new();
+    g.write_indexed_png(&mut buf, 100, 16).unwrap();
+    let (width, color_type, palette, trns, data) = decode(&buf);
+    assert_eq!(width, 100);
+    assert_eq!(color_type, png::ColorType::Indexed);
+    assert_eq!(palette.len(), 16 * 3);
+    assert_eq!(trns, None);
+    assert_eq!(data.len(), 100);
+    assert_eq!(&palette[..3], &[255, 0, 0]);
+    assert_eq!(&palette[45..], &[0, 0, 255]);
+    assert_eq!((data[0], data[99]), (0, 15));
+    assert!(data.windows(2).all(|w| w[0] <= w[1]));
+
+    for (i, c) in g.colors(16).iter().enumerate() {
+        assert_eq!(palette[i * 3..i * 3 + 3], c.to_rgba8()[..3]);
+    }
+
+    // transparency
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f0"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let mut buf = Vec::new();
+    g.write_indexed_png(&mut buf, 10, 3).unwrap();
+    let (_, _, palette, trns, _) = decode(&buf);
+    assert_eq!(palette.len(), 9);
+    assert_eq!(trns, Some(vec![255, 128, 0]));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn as_value_colorizer() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let f = g.as_value_colorizer(100.0, 300.0);
+    assert_eq!(f(100.0), image::Rgba([255, 0, 0, 255]));
+    assert_eq!(f(200.0), image::Rgba([0, 255, 0, 255]));
+    assert_eq!(f(300.0), image::Rgba([0, 0, 255, 255]));
+    assert_eq!(f(150.0), image::Rgba(g.at(-0.5).to_rgba8()));
+
+    // outside the data range
+    assert_eq!(f(0.0), image::Rgba([255, 0, 0, 255]));
+    assert_eq!(f(1000.0), image::Rgba([0, 0, 255, 255]));
+
+    // reversed data range
+    let f = g.as_value_colorizer(1.0, 0.0);
+    assert_eq!(f(1.0), image::Rgba([255, 0, 0, 255]));
+    assert_eq!(f(0.0), image::Rgba([0, 0, 255, 255]));
+
+    // empty data range
+    let f = g.as_value_colorizer(5.0, 5.0);
+    assert_eq!(f(5.0), image::Rgba([255, 0, 0, 255]));
+    assert_eq!(f(9.0), image::Rgba([255, 0, 0, 255]));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn to_legend_image() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let img = g.to_legend_image(100, 8, 4);
+    assert_eq!(img.dimensions(), (100, 8));
+
+    // separators at the band edges
+    for x in [25, 50, 75] {
+        for y in 0..8 {
+            assert_eq!(img.get_pixel(x, y).0, [0, 0, 0, 255]);
+        }
+    }
+
+    let mut colors = Vec::new();
+    for x in 0..100 {
+        let px = img.get_pixel(x, 0).0;
+        assert_eq!(img.get_pixel(x, 7).0, px);
+        if px != [0, 0, 0, 255] && !colors.contains(&px) {
+            colors.push(px);
+        }
+    }
+    assert_eq!(colors.len(), 4);
+    for (i, c) in colors.iter().enumerate() {
+        assert_eq!(*c, g.at_index(i, 4).to_rgba8());
+    }
+
+    assert_eq!(img.get_pixel(0, 0).0, g.at(0.125).to_rgba8());
+    assert_eq!(img.get_pixel(99, 0).0, g.at(0.875).to_rgba8());
+
+    // a single band has no separator
+    let img = g.to_legend_image(10, 1, 1);
+    assert!(img.pixels().all(|p| p.0 == g.at(0.5).to_rgba8()));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn render_image() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 2.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let img = g.render_image(20, 10, |x, y| (x as f32 / 19.0) + (y as f32 / 9.0));
+    assert_eq!(img.dimensions(), (20, 10));
+    assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(19, 0).0, [0, 255, 0, 255]);
+    assert_eq!(img.get_pixel(0, 9).0, [0, 255, 0, 255]);
+    assert_eq!(img.get_pixel(19, 9).0, [0, 0, 255, 255]);
+
+    for (x, y, px) in img.enumerate_pixels() {
+        let t = (x as f32 / 19.0) + (y as f32 / 9.0);
+        assert_eq!(px.0, g.at(t).to_rgba8());
+    }
+
+    let img = g.render_image(0, 5, |_, _| 0.0);
+    assert_eq!(img.dimensions(), (0, 5));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn to_image_linear_avg() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .build::<LinearGradient>()
+        .unwrap()
+        .sharp(2, 0.0);
+
+    let row = g.to_image_linear_avg(3, 4);
+    assert_eq!(row.len(), 12);
+    assert_eq!(&row[0..4], &[0, 0, 0, 255]);
+    // half black, half white, averaged in linear light instead of sRGB (128)
+    assert_eq!(&row[4..8], &[188, 188, 188, 255]);
+    assert_eq!(&row[8..12], &[255, 255, 255, 255]);
+
+    // without supersampling, the center of every pixel
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+    let row = g.to_image_linear_avg(4, 1);
+    for (x, px) in row.chunks(4).enumerate() {
+        assert_eq!(px, g.at((x as f32 + 0.5) / 4.0).to_rgba8());
+    }
+
+    assert!(g.to_image_linear_avg(0, 4).is_empty());
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn rgba_f16() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f", "#fff0"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    for t in [-2.0, -1.0, -0.7, -0.3, 0.0, 0.1, 0.5, 0.99, 1.0, 2.0] {
+        let a = g.at(t).to_array();
+        let b = g.at_rgba_f16(t);
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b.to_f32()).abs() < 1e-3);
+        }
+    }
+
+    let buf = g.to_rgba_f16_buffer(11);
+    assert_eq!(buf.len(), 44);
+
+    for (c, b) in g.colors(11).iter().zip(buf.chunks(4)) {
+        for (a, b) in c.to_array().iter().zip(b) {
+            assert!((a - b.to_f32()).abs() < 1e-3);
+        }
+    }
+
+    assert!(g.to_rgba_f16_buffer(0).is_empty());
+}
+
+#[test]
+fn to_cube_lut_1d() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.to_cube_lut_1d(16);
+    let mut lines = s.lines();
+    assert_eq!(lines.next(), Some("LUT_1D_SIZE 16"));
+
+    let rows = lines.collect::<Vec<_>>();
+    assert_eq!(rows.len(), 16);
+    assert_eq!(rows[0], "1.000000 0.000000 0.000000");
+    assert_eq!(rows[15], "0.000000 0.000000 1.000000");
+
+    for row in rows {
+        let v = row
+            .split(' ')
+            .map(|x| x.parse::<f32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(v.len(), 3);
+        assert!(v.iter().all(|x| (0.0..=1.0).contains(x)));
+    }
+}
@@ -11,18 +11,32 @@ fn basic() {
         .build::<colorgrad::BasisGradient>()
         .unwrap();
 
-    assert_eq!(g.at(0.00).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(0.25).to_hex_string(), "#857505");
-    assert_eq!(g.at(0.50).to_hex_string(), "#2baa2b");
-    assert_eq!(g.at(0.75).to_hex_string(), "#057585");
-    assert_eq!(g.at(1.00).to_hex_string(), "#0000ff");
+    assert_eq!(g.at(0.00).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.25).to_css_hex(), "#857505");
+    assert_eq!(g.at(0.50).to_css_hex(), "#2baa2b");
+    assert_eq!(g.at(0.75).to_css_hex(), "#057585");
+    assert_eq!(g.at(1.00).to_css_hex(), "#0000ff");
 
     assert_eq!(
         colors2hex(&g.colors(5)),
         &["#ff0000", "#857505", "#2baa2b", "#057585", "#0000ff"]
     );
 
-    assert_eq!(g.at(-0.1).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(1.11).to_hex_string(), "#0000ff");
-    assert_eq!(g.at(f32::NAN).to_hex_string(), "#000000");
+    assert_eq!(g.at(-0.1).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(1.11).to_css_hex(), "#0000ff");
+    assert_eq!(g.at(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn snap_to_stop() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(colorgrad::BlendMode::Oklab)
+        .build::<colorgrad::BasisGradient>()
+        .unwrap();
+
+    assert_eq!(g.snap_to_stop(0.1).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.4).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.6).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.8).to_rgba8(), [0, 0, 255, 255]);
 }
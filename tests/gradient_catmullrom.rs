@@ -11,18 +11,99 @@ fn basic() {
         .build::<colorgrad::CatmullRomGradient>()
         .unwrap();
 
-    assert_eq!(g.at(0.00).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(0.25).to_hex_string(), "#609f00");
-    assert_eq!(g.at(0.50).to_hex_string(), "#00ff00");
-    assert_eq!(g.at(0.75).to_hex_string(), "#009f60");
-    assert_eq!(g.at(1.00).to_hex_string(), "#0000ff");
+    assert_eq!(g.at(0.00).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.25).to_css_hex(), "#609f00");
+    assert_eq!(g.at(0.50).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(0.75).to_css_hex(), "#009f60");
+    assert_eq!(g.at(1.00).to_css_hex(), "#0000ff");
 
     assert_eq!(
         colors2hex(&g.colors(5)),
         &["#ff0000", "#609f00", "#00ff00", "#009f60", "#0000ff"]
     );
 
-    assert_eq!(g.at(-0.1).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(1.11).to_hex_string(), "#0000ff");
-    assert_eq!(g.at(f32::NAN).to_hex_string(), "#000000");
+    assert_eq!(g.at(-0.1).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(1.11).to_css_hex(), "#0000ff");
+    assert_eq!(g.at(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn gamut_map_oklch() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#fff", "#f00", "#00f", "#000"])
+        .mode(colorgrad::BlendMode::Oklab)
+        .build::<colorgrad::CatmullRomGradient>()
+        .unwrap();
+
+    let gm = g.gamut_map_oklch();
+    assert_eq!(gm.domain(), g.domain());
+
+    let hue_diff = |a: f32, b: f32| {
+        let d = (a - b).to_degrees().rem_euclid(360.0);
+        d.min(360.0 - d)
+    };
+
+    for t in [0.1, 0.15, 0.2, 0.75, 0.8] {
+        let c = g.at(t);
+        // over-saturated
+        assert!([c.r, c.g, c.b].iter().any(|v| *v < 0.0 || *v > 1.0));
+
+        let m = gm.at(t);
+        assert!([m.r, m.g, m.b].iter().all(|v| (0.0..=1.0).contains(v)));
+
+        let h = c.to_oklcha()[2];
+        let h_clamp = c.clamp().to_oklcha()[2];
+        let h_map = m.to_oklcha()[2];
+        assert!(hue_diff(h, h_map) < hue_diff(h, h_clamp));
+    }
+
+    // colors inside the gamut are untouched
+    for t in [0.0, 0.4, 0.5, 0.6, 1.0] {
+        assert_eq!(gm.at(t).to_array(), g.at(t).to_array());
+    }
+}
+
+#[test]
+fn snap_to_stop() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .mode(colorgrad::BlendMode::LinearRgb)
+        .build::<colorgrad::CatmullRomGradient>()
+        .unwrap();
+
+    assert_eq!(g.snap_to_stop(0.1).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.4).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.6).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(g.snap_to_stop(0.8).to_rgba8(), [0, 0, 255, 255]);
+}
+
+#[test]
+fn out_of_gamut_positions() {
+    let build = |mode| {
+        colorgrad::GradientBuilder::new()
+            .html_colors(&["#00f", "#0f0", "#f00"])
+            .mode(mode)
+            .build::<colorgrad::CatmullRomGradient>()
+            .unwrap()
+    };
+
+    let g = build(colorgrad::BlendMode::Oklab);
+    let positions = g.out_of_gamut_positions(101);
+    assert!(!positions.is_empty());
+
+    for t in &positions {
+        let c = g.at(*t);
+        assert!([c.r, c.g, c.b].iter().any(|v| *v < 0.0 || *v > 1.0));
+    }
+
+    // the colors themselves are in gamut
+    assert!(!positions.contains(&0.0));
+    assert!(!positions.contains(&0.5));
+    assert!(!positions.contains(&1.0));
+
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#00f", "#0f0", "#f00"])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    assert!(g.out_of_gamut_positions(101).is_empty());
 }
@@ -138,3 +138,107 @@ fn invalid_format() {
         assert!(res.is_err());
     }
 }
+
+#[cfg(feature = "ggr")]
+#[test]
+fn parse_from_memory() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    let ggr = "GIMP Gradient\nName: My Gradient\n1\n0 0.5 1 0 0 0 1 1 1 1 1 0 0 1 3";
+
+    let grad = GimpGradient::from_str(ggr, &red, &blue).unwrap();
+    assert_eq!(grad.name(), "My Gradient");
+    assert_eq!(grad.at(0.0).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(grad.at(1.0).to_rgba8(), [0, 0, 255, 255]);
+
+    let grad = GimpGradient::from_bytes(ggr.as_bytes(), &red, &blue).unwrap();
+    assert_eq!(grad.name(), "My Gradient");
+    assert_eq!(grad.at(0.0).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(grad.at(1.0).to_rgba8(), [0, 0, 255, 255]);
+
+    let grad =
+        GimpGradient::from_bytes(include_bytes!("../examples/ggr/UTF_8_BOM.ggr"), &red, &blue);
+    assert!(grad.is_ok());
+
+    let res = GimpGradient::from_str("GIMP Gradient\nName: Gradient\n0", &red, &blue);
+    assert_eq!(res.unwrap_err().to_string(), "no segment (line 4)");
+}
+
+#[cfg(feature = "ggr")]
+#[test]
+fn rebuild_from_gradient() {
+    use colorgrad::{BasisGradient, GradientBuilder};
+
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let ggr = "GIMP Gradient\nName: Gradient\n2\n\
+        0 0.25 0.5 1 1 1 1 1 0 0 1 0 0 0 0\n\
+        0.5 0.75 1 1 0 0 1 0 0 0 1 0 0 0 0";
+    let grad = GimpGradient::from_str(ggr, &red, &blue).unwrap();
+
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#0f0"]).css("#000, #fff");
+    let basis = gb.from_gradient(&grad, 9).build::<BasisGradient>().unwrap();
+
+    assert_eq!(gb.get_colors().len(), 9);
+    assert_eq!(gb.get_positions().len(), 9);
+    assert_eq!(basis.domain(), grad.domain());
+    assert_eq!(basis.at(0.0).to_rgba8(), [255, 255, 255, 255]);
+    // smoothed, the basis spline does not pass through the interior colors
+    assert_ne!(basis.at(0.5).to_rgba8(), grad.at(0.5).to_rgba8());
+    assert!(basis.at(0.5).r > 0.9);
+    assert_eq!(basis.at(1.0).to_rgba8(), [0, 0, 0, 255]);
+}
+
+#[cfg(feature = "ggr")]
+#[test]
+fn curved_segment_edges() {
+    let col = Color::default();
+
+    // curved red to lime, then linear lime to blue
+    let ggr = "GIMP Gradient\nName: Curved\n2\n\
+        0 0.3 0.6 1 0 0 1 0 1 0 1 1 0\n\
+        0.6 0.8 1 0 1 0 1 0 0 1 1 0 0\n";
+    let grad = GimpGradient::from_str(ggr, &col, &col).unwrap();
+    for t in [0.0, f32::EPSILON] {
+        let c = grad.at(t);
+        assert!(c.to_array().iter().all(|v| v.is_finite()));
+        assert_eq!(c.to_rgba8(), [255, 0, 0, 255]);
+    }
+    assert_eq!(grad.at(0.3).to_rgba8(), [128, 128, 0, 255]);
+    assert_eq!(grad.at(0.6).to_rgba8(), [0, 255, 0, 255]);
+
+    // HSV coloring
+    let ggr = "GIMP Gradient\nName: Curved HSV\n1\n0 0.25 1 1 0 0 1 0 0 1 1 1 1\n";
+    let grad = GimpGradient::from_str(ggr, &col, &col).unwrap();
+    assert_eq!(grad.at(0.0).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(grad.at(1.0).to_rgba8(), [0, 0, 255, 255]);
+}
+
+#[cfg(feature = "ggr")]
+#[test]
+fn hsv_same_hue() {
+    let col = Color::default();
+
+    // red to dark red, HSV counter-clockwise and clockwise
+    for coloring in [1, 2] {
+        let ggr = format!(
+            "GIMP Gradient\nName: Same hue\n1\n0 0.5 1 1 0 0 1 0.5 0 0 1 0 {}\n",
+            coloring
+        );
+        let grad = GimpGradient::from_str(&ggr, &col, &col).unwrap();
+
+        for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            let [r, g, b, _] = grad.at(t).to_rgba8();
+            assert!(r > 0);
+            assert_eq!((g, b), (0, 0));
+        }
+        assert_eq!(grad.at(0.5).to_css_hex(), "#bf0000");
+    }
+
+    // hue 360 and hue 0 are the same hue
+    let ggr = "GIMP Gradient\nName: Yellow\n1\n0 0.5 1 1 1 0 1 1 1 0.5 1 0 1\n";
+    let grad = GimpGradient::from_str(ggr, &col, &col).unwrap();
+    assert_eq!(grad.at(0.5).to_rgba8()[..2], [255, 255]);
+}
@@ -3,6 +3,10 @@ use colorgrad::Gradient;
 mod utils;
 use utils::*;
 
+// Hex colors only, so the tests using it run without the `named-colors` feature: stops
+// with and without position, an interpolation hint and a transparent color
+const CSS_FIXTURE: &str = "#ffd700, #f00 20%, 50%, #00f, #0f08 90%, #ff1493";
+
 #[test]
 fn basic() {
     let g = colorgrad::GradientBuilder::new()
@@ -11,18 +15,614 @@ fn basic() {
         .build::<colorgrad::LinearGradient>()
         .unwrap();
 
-    assert_eq!(g.at(0.00).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(0.25).to_hex_string(), "#808000");
-    assert_eq!(g.at(0.50).to_hex_string(), "#00ff00");
-    assert_eq!(g.at(0.75).to_hex_string(), "#008080");
-    assert_eq!(g.at(1.00).to_hex_string(), "#0000ff");
+    assert_eq!(g.at(0.00).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(0.25).to_css_hex(), "#808000");
+    assert_eq!(g.at(0.50).to_css_hex(), "#00ff00");
+    assert_eq!(g.at(0.75).to_css_hex(), "#008080");
+    assert_eq!(g.at(1.00).to_css_hex(), "#0000ff");
 
     assert_eq!(
         colors2hex(&g.colors(5)),
         &["#ff0000", "#808000", "#00ff00", "#008080", "#0000ff"]
     );
 
-    assert_eq!(g.at(-0.1).to_hex_string(), "#ff0000");
-    assert_eq!(g.at(1.11).to_hex_string(), "#0000ff");
-    assert_eq!(g.at(f32::NAN).to_hex_string(), "#000000");
+    assert_eq!(g.at(-0.1).to_css_hex(), "#ff0000");
+    assert_eq!(g.at(1.11).to_css_hex(), "#0000ff");
+    assert_eq!(g.at(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn many_stops() {
+    // more stops than the inline capacity of the `smallvec` feature
+    let colors = [
+        "#000", "#f00", "#0f0", "#00f", "#ff0", "#0ff", "#f0f", "#fff", "#000", "#f00", "#0f0",
+        "#00f",
+    ];
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&colors)
+        .domain(&[0.0, 11.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        colors2hex(&g.colors(12)),
+        &[
+            "#000000", "#ff0000", "#00ff00", "#0000ff", "#ffff00", "#00ffff", "#ff00ff", "#ffffff",
+            "#000000", "#ff0000", "#00ff00", "#0000ff",
+        ]
+    );
+    assert_eq!(g.at(0.5).to_css_hex(), "#800000");
+    assert_eq!(g.at(10.5).to_css_hex(), "#008080");
+}
+
+#[test]
+fn snap_to_stop() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.3, 1.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let data = [
+        (-1.0, "#ff0000"),
+        (0.0, "#ff0000"),
+        (0.1, "#ff0000"),
+        (0.15, "#ff0000"),
+        (0.2, "#00ff00"),
+        (0.3, "#00ff00"),
+        (0.6, "#00ff00"),
+        (0.7, "#0000ff"),
+        (1.0, "#0000ff"),
+        (2.0, "#0000ff"),
+    ];
+    for (t, hex) in data {
+        assert_eq!(g.snap_to_stop(t).to_css_hex(), hex);
+    }
+    assert_eq!(g.snap_to_stop(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn channel_modes() {
+    use colorgrad::ChannelMode::{Circular, Linear};
+
+    // hue interpolated circularly
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#f0f"])
+        .channel_modes([Circular, Linear, Linear])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        colors2hex(&g.colors(5)),
+        &["#ff0000", "#ff0040", "#ff0080", "#ff00bf", "#ff00ff"]
+    );
+
+    // plain HSL
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#f0f"])
+        .channel_modes([Linear, Linear, Linear])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    assert_eq!(
+        colors2hex(&g.colors(5)),
+        &["#ff0000", "#bfff00", "#00ff80", "#0040ff", "#ff00ff"]
+    );
+
+    // lightness is interpolated linearly in both cases
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["hsl(350, 100%, 20%)", "hsl(10, 100%, 60%)"])
+        .channel_modes([Circular, Linear, Linear])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let [h, s, l, _] = g.at(0.5).to_hsla();
+    assert!(h.abs() < 0.01 || (h - 360.0).abs() < 0.01);
+    assert!((s - 1.0).abs() < 0.01);
+    assert!((l - 0.4).abs() < 0.01);
+}
+
+#[test]
+fn hue_direction() {
+    use colorgrad::ChannelMode::{Circular, Linear};
+    use colorgrad::HueDirection::{self, *};
+
+    // total hue change, in degrees, between samples of the gradient
+    let sweep = |colors: &[&str], dir: HueDirection| {
+        let g = colorgrad::GradientBuilder::new()
+            .html_colors(colors)
+            .channel_modes([Circular, Linear, Linear])
+            .hue_direction(dir)
+            .build::<colorgrad::LinearGradient>()
+            .unwrap();
+        let hues = g
+            .colors(101)
+            .iter()
+            .map(|c| c.to_hsla()[0])
+            .collect::<Vec<_>>();
+        hues.windows(2)
+            .map(|h| {
+                let d = (h[1] - h[0]).rem_euclid(360.0);
+                if d > 180.0 {
+                    d - 360.0
+                } else {
+                    d
+                }
+            })
+            .sum::<f32>()
+    };
+    let near = |a: f32, b: f32| (a - b).abs() < 1.0;
+
+    // hue 20 to hue 40
+    let colors = &["hsl(20, 100%, 50%)", "hsl(40, 100%, 50%)"];
+    assert!(near(sweep(colors, Shorter), 20.0));
+    assert!(near(sweep(colors, Longer), -340.0));
+    assert!(near(sweep(colors, Increasing), 20.0));
+    assert!(near(sweep(colors, Decreasing), -340.0));
+
+    // hue 40 to hue 20
+    let colors = &["hsl(40, 100%, 50%)", "hsl(20, 100%, 50%)"];
+    assert!(near(sweep(colors, Shorter), -20.0));
+    assert!(near(sweep(colors, Longer), 340.0));
+    assert!(near(sweep(colors, Increasing), 340.0));
+    assert!(near(sweep(colors, Decreasing), -20.0));
+
+    // equal hues
+    let colors = &["hsl(40, 100%, 30%)", "hsl(40, 100%, 70%)"];
+    for dir in [Shorter, Longer, Increasing, Decreasing] {
+        assert!(near(sweep(colors, dir), 0.0));
+    }
+
+    // default
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#f0f"])
+        .channel_modes([Circular, Linear, Linear])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.5).to_css_hex(), "#ff0080");
+}
+
+#[cfg(feature = "lab")]
+#[test]
+fn hue_direction_lch() {
+    use colorgrad::{
+        BasisGradient, BlendMode, CatmullRomGradient, GradientBuilder, HueDirection, LinearGradient,
+    };
+
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f30", "#f60"]).mode(BlendMode::Lch);
+
+    let hue_at_middle = |gb: &mut GradientBuilder| {
+        let h0 = gb.get_colors()[0].to_lcha()[2].to_degrees();
+        let g = gb.build::<LinearGradient>().unwrap();
+        let h = g.at(0.5).to_lcha()[2].to_degrees();
+        let d = (h - h0).rem_euclid(360.0);
+        let basis = gb.build::<BasisGradient>().unwrap().at(0.5).to_lcha()[2];
+        let catmull = gb.build::<CatmullRomGradient>().unwrap().at(0.5).to_lcha()[2];
+        assert!((basis.to_degrees() - h).abs() < 1.0);
+        assert!((catmull.to_degrees() - h).abs() < 1.0);
+        d
+    };
+
+    // nearby hues, the short way stays close to the first hue
+    let d = hue_at_middle(&mut gb);
+    assert!(d < 20.0);
+
+    // the long way goes to the opposite side of the wheel
+    let d = hue_at_middle(gb.hue_direction(HueDirection::Longer));
+    assert!(d > 150.0 && d < 210.0);
+}
+
+#[test]
+fn average_color() {
+    use colorgrad::BlendMode;
+
+    let n = 100_000;
+
+    for mode in [BlendMode::Rgb, BlendMode::LinearRgb, BlendMode::Oklab] {
+        let mut gb = colorgrad::GradientBuilder::new();
+        gb.css(CSS_FIXTURE).domain(&[-3.0, 7.0]).mode(mode);
+        let g = gb.build::<colorgrad::LinearGradient>().unwrap();
+
+        // dense numerical average (midpoint rule) in the blending color space
+        let mut sum = [0.0f64; 4];
+        for i in 0..n {
+            let t = -3.0 + (i as f64 + 0.5) / n as f64 * 10.0;
+            let c = g.at(t as f32);
+            let v = match mode {
+                BlendMode::Rgb => c.to_array(),
+                BlendMode::LinearRgb => c.to_linear_rgba(),
+                _ => c.to_oklaba(),
+            };
+            for (s, v) in sum.iter_mut().zip(v) {
+                *s += v as f64 / n as f64;
+            }
+        }
+
+        let c = g.average_color();
+        let v = match mode {
+            BlendMode::Rgb => c.to_array(),
+            BlendMode::LinearRgb => c.to_linear_rgba(),
+            _ => c.to_oklaba(),
+        };
+        for (a, b) in v.iter().zip(sum) {
+            assert!((*a as f64 - b).abs() < 1e-4, "{:?} {:?}", v, sum);
+        }
+    }
+}
+
+#[test]
+fn average_color_circular_hue() {
+    use colorgrad::ChannelMode::{Circular, Linear};
+
+    // hues around 0 degree
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&[
+            "hsl(340, 100%, 50%)",
+            "hsl(350, 100%, 50%)",
+            "hsl(10, 100%, 50%)",
+            "hsl(20, 100%, 50%)",
+        ])
+        .channel_modes([Circular, Linear, Linear])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let [h, s, l, a] = g.average_color().to_hsla();
+    assert!(!(0.5..=359.5).contains(&h), "{}", h);
+    assert!((s - 1.0).abs() < 1e-4);
+    assert!((l - 0.5).abs() < 1e-4);
+    assert_eq!(a, 1.0);
+
+    // compared to a dense numerical average, with a segment sweeping more than 180 degree
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&[
+            "hsl(0, 100%, 50%)",
+            "hsl(270, 80%, 40%)",
+            "hsl(300, 60%, 60%)",
+        ])
+        .domain(&[0.0, 0.3, 1.0])
+        .channel_modes([Circular, Linear, Linear])
+        .hue_direction(colorgrad::HueDirection::Increasing)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let n = 10000;
+    let (mut x, mut y, mut s, mut l) = (0.0, 0.0, 0.0, 0.0);
+    for i in 0..n {
+        let [h, s1, l1, _] = g.at((i as f32 + 0.5) / n as f32).to_hsla();
+        x += h.to_radians().cos();
+        y += h.to_radians().sin();
+        s += s1;
+        l += l1;
+    }
+    let h = y.atan2(x).to_degrees().rem_euclid(360.0);
+
+    let avg = g.average_color().to_hsla();
+    assert!((avg[0] - h).abs() < 0.5, "{} {}", avg[0], h);
+    assert!((avg[1] - s / n as f32).abs() < 1e-3);
+    assert!((avg[2] - l / n as f32).abs() < 1e-3);
+}
+
+#[test]
+fn scanline_sampler() {
+    let g = colorgrad::GradientBuilder::new()
+        .css(CSS_FIXTURE)
+        .domain(&[-3.0, 7.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let mut sampler = g.scanline_sampler();
+
+    // increasing
+    for i in 0..=1200 {
+        let t = -4.0 + i as f32 / 100.0;
+        assert_eq!(sampler.at(t).to_array(), g.at(t).to_array());
+    }
+
+    // decreasing and random order
+    for t in [6.5, 2.0, 2.0, -2.9, 0.0, 5.9, 5.95, 1.0, -10.0, 10.0, 3.3] {
+        assert_eq!(sampler.at(t).to_array(), g.at(t).to_array());
+    }
+
+    assert_eq!(sampler.at(f32::NAN).to_css_hex(), "#000000");
+}
+
+#[test]
+fn at_sorted() {
+    let g = colorgrad::GradientBuilder::new()
+        .css(CSS_FIXTURE)
+        .domain(&[-3.0, 7.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let ts = [
+        -5.0, -3.0, -3.0, -1.0, 0.0, 0.5, 0.5, 2.0, 5.5, 6.0, 6.99, 7.0, 9.0,
+    ];
+    let mut out = vec![colorgrad::Color::default(); ts.len()];
+    g.at_sorted(&ts, &mut out);
+
+    for (t, c) in ts.iter().zip(&out) {
+        assert_eq!(c.to_array(), g.at(*t).to_array());
+    }
+
+    g.at_sorted(&[], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn at_sorted_length_mismatch() {
+    let g = colorgrad::GradientBuilder::new()
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    let mut out = vec![colorgrad::Color::default(); 2];
+    g.at_sorted(&[0.0, 0.5, 1.0], &mut out);
+}
+
+#[test]
+fn two_color() {
+    let a = colorgrad::Color::new(0.2, 0.4, 0.6, 0.8);
+    let b = colorgrad::Color::from_rgba8(255, 0, 128, 255);
+
+    for mode in [
+        colorgrad::BlendMode::Rgb,
+        colorgrad::BlendMode::LinearRgb,
+        colorgrad::BlendMode::Oklab,
+    ] {
+        let g = colorgrad::two_color(a.clone(), b.clone(), mode);
+        assert_eq!(g.domain(), (0.0, 1.0));
+        assert_eq!(g.at(0.0), a);
+        assert_eq!(g.at(1.0), b);
+
+        let g2 = colorgrad::GradientBuilder::new()
+            .colors(&[a.clone(), b.clone()])
+            .mode(mode)
+            .build::<colorgrad::LinearGradient>()
+            .unwrap();
+
+        for t in [0.1, 0.25, 0.5, 0.8] {
+            assert_eq!(g.at(t), g2.at(t));
+        }
+    }
+}
+
+#[cfg(feature = "lab")]
+#[test]
+fn lch_blend_mode() {
+    use colorgrad::{BasisGradient, BlendMode, CatmullRomGradient, GradientBuilder};
+
+    let build = |mode| {
+        GradientBuilder::new()
+            .html_colors(&["#f00", "#0f0"])
+            .mode(mode)
+            .build::<colorgrad::LinearGradient>()
+            .unwrap()
+    };
+    let lab = build(BlendMode::Lab);
+    let lch = build(BlendMode::Lch);
+
+    assert_eq!(lch.at(0.0).to_css_hex(), "#ff0000");
+    assert_eq!(lch.at(1.0).to_css_hex(), "#00ff00");
+
+    // Lch keeps the chroma, Lab goes through a desaturated color
+    let c_lab = lab.at(0.5).to_lcha()[1];
+    let c_lch = lch.at(0.5).to_lcha()[1];
+    assert!(c_lch > c_lab);
+
+    let [_, c0, _, _] = lch.at(0.0).to_lcha();
+    let [_, c1, _, _] = lch.at(1.0).to_lcha();
+    assert!((c_lch - (c0 + c1) / 2.0).abs() < 1.0);
+
+    // hue goes along the shortest arc, across 0 degree
+    let g = GradientBuilder::new()
+        .html_colors(&["#f0a", "#f50"])
+        .mode(BlendMode::Lch)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    let h0 = g.at(0.0).to_lcha()[2].to_degrees();
+    let h1 = g.at(1.0).to_lcha()[2].to_degrees();
+    let hm = g.at(0.5).to_lcha()[2].to_degrees();
+    let d = |a: f32, b: f32| (a - b + 180.0).rem_euclid(360.0) - 180.0;
+    assert!(d(hm, h0).abs() < d(h1, h0).abs());
+    assert!(d(h1, hm).abs() < d(h1, h0).abs());
+
+    // white and gray have no hue, fading from or to gray keeps the hue
+    let g = GradientBuilder::new()
+        .html_colors(&["#fff", "#00f", "#777"])
+        .mode(BlendMode::Lch)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    let hue = g.at(0.5).to_lcha()[2].to_degrees();
+    for t in [0.1, 0.25, 0.4, 0.6, 0.75, 0.9] {
+        let [_, _, h, _] = g.at(t).to_lcha();
+        assert!(d(h.to_degrees(), hue).abs() < 0.1);
+    }
+
+    // spline gradients
+    let colors = ["#f0a", "#f50", "#0f0", "#00f"];
+    let basis = GradientBuilder::new()
+        .html_colors(&colors)
+        .mode(BlendMode::Lch)
+        .build::<BasisGradient>()
+        .unwrap();
+    let catmull = GradientBuilder::new()
+        .html_colors(&colors)
+        .mode(BlendMode::Lch)
+        .build::<CatmullRomGradient>()
+        .unwrap();
+    assert_eq!(basis.at(0.0).to_css_hex(), "#ff00aa");
+    assert_eq!(basis.at(1.0).to_css_hex(), "#0000ff");
+    assert_eq!(catmull.at(0.0).to_css_hex(), "#ff00aa");
+    assert_eq!(catmull.at(1.0 / 3.0).to_css_hex(), "#ff5500");
+    assert_eq!(catmull.at(1.0).to_css_hex(), "#0000ff");
+}
+
+#[test]
+fn hsl_blend_mode() {
+    use colorgrad::{BasisGradient, BlendMode, CatmullRomGradient, GradientBuilder};
+
+    let mut gb = GradientBuilder::new();
+    gb.html_colors(&["#f00", "#00f"]).mode(BlendMode::Hsl);
+
+    // red to blue through magenta, not through green
+    let linear = gb.build::<colorgrad::LinearGradient>().unwrap();
+    assert_eq!(linear.mode(), Some(BlendMode::Hsl));
+    assert_eq!(
+        colors2hex(&linear.colors(5)),
+        &["#ff0000", "#ff0080", "#ff00ff", "#8000ff", "#0000ff"]
+    );
+
+    let catmull = gb.build::<CatmullRomGradient>().unwrap();
+    assert_eq!(catmull.at(0.5).to_css_hex(), "#ff00ff");
+
+    let basis = gb.build::<BasisGradient>().unwrap();
+    for g in [&linear as &dyn Gradient, &catmull, &basis] {
+        for c in g.colors(21) {
+            assert!(c.g < 1e-4);
+        }
+    }
+
+    // across the 0 degree hue, both directions
+    let g = GradientBuilder::new()
+        .html_colors(&["hsl(330, 100%, 50%)", "hsl(30, 100%, 50%)"])
+        .mode(BlendMode::Hsl)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.5).to_css_hex(), "#ff0000");
+
+    let g = GradientBuilder::new()
+        .html_colors(&["hsl(30, 100%, 50%)", "hsl(330, 100%, 50%)"])
+        .mode(BlendMode::Hsl)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.5).to_css_hex(), "#ff0000");
+
+    // the saturated midpoint is brighter than the RGB one
+    let rgb = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0"])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    let hsl = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0"])
+        .mode(BlendMode::Hsl)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    assert_eq!(rgb.at(0.5).to_css_hex(), "#808000");
+    assert_eq!(hsl.at(0.5).to_css_hex(), "#ffff00");
+
+    // gray has no hue, fading to gray keeps the hue
+    let g = GradientBuilder::new()
+        .html_colors(&["#fff", "#00f", "#000"])
+        .mode(BlendMode::Hsl)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+    for c in g.colors(21) {
+        assert!(c.r == c.g && c.r <= c.b);
+    }
+
+    // interpolation hint
+    let g = GradientBuilder::new()
+        .css("#f00, 25%, #00f")
+        .mode(BlendMode::Hsl)
+        .build::<CatmullRomGradient>()
+        .unwrap();
+    assert_eq!(g.at(0.25).to_css_hex(), "#ff00ff");
+}
+
+#[test]
+fn segments() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.3, 1.0])
+        .mode(colorgrad::BlendMode::Oklab)
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let segments = g.segments();
+    assert_eq!(segments.len(), 2);
+
+    let (p0, c0, p1, c1) = &segments[0];
+    assert_eq!((*p0, *p1), (0.0, 0.3));
+    assert_eq!(c0.to_css_hex(), "#ff0000");
+    assert_eq!(c1.to_css_hex(), "#00ff00");
+
+    let (p0, c0, p1, c1) = &segments[1];
+    assert_eq!((*p0, *p1), (0.3, 1.0));
+    assert_eq!(c0.to_css_hex(), "#00ff00");
+    assert_eq!(c1.to_css_hex(), "#0000ff");
+}
+
+#[test]
+fn sharp_stops() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 0.8, 1.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let s = g.sharp_stops(0.0);
+    assert_eq!(s.domain(), (-1.0, 1.0));
+    assert_eq!(colors2hex(&s.colors(3)), &["#ff0000", "#00ff00", "#0000ff"]);
+    assert_eq!(s.at(-0.4).to_css_hex(), "#ff0000");
+    assert_eq!(s.at(0.3).to_css_hex(), "#00ff00");
+    assert_eq!(s.at(0.4).to_css_hex(), "#0000ff");
+
+    let mut bands = Vec::new();
+    for c in s.colors(300) {
+        let hex = c.to_css_hex();
+        if !bands.contains(&hex) {
+            bands.push(hex);
+        }
+    }
+    assert_eq!(bands, &["#ff0000", "#00ff00", "#0000ff"]);
+
+    // sharp() samples the gradient instead
+    assert_ne!(g.sharp(3, 0.0).at(0.0).to_css_hex(), "#00ff00");
+}
+
+#[test]
+fn move_stop() {
+    let g = colorgrad::GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[0.0, 0.5, 1.0])
+        .build::<colorgrad::LinearGradient>()
+        .unwrap();
+
+    let m = g.move_stop(1, 0.2);
+    assert_eq!(m.at(0.2).to_css_hex(), "#00ff00");
+    assert_eq!(m.segments()[0].2, 0.2);
+    assert_eq!(m.domain(), (0.0, 1.0));
+
+    // moving past a neighbor clamps instead of reordering
+    let m = g.move_stop(1, 1.5);
+    let s = m.segments();
+    assert!(s[0].2 < 1.0);
+    assert!(s[0].2 > 0.999);
+    assert_eq!(s[1].2, 1.0);
+    assert_eq!(
+        colors2hex(&[s[0].1.clone(), s[1].1.clone(), s[1].3.clone()]),
+        &["#ff0000", "#00ff00", "#0000ff"]
+    );
+    assert_eq!(
+        m.at(0.5).to_rgba8(),
+        g.move_stop(1, 0.99999).at(0.5).to_rgba8()
+    );
+
+    let m = g.move_stop(1, -3.0);
+    assert!(m.segments()[0].2 > 0.0);
+    assert_eq!(m.at(0.0).to_css_hex(), "#ff0000");
+
+    // first and last stops change the domain
+    assert_eq!(g.move_stop(0, -1.0).domain(), (-1.0, 1.0));
+    assert_eq!(g.move_stop(2, 4.0).domain(), (0.0, 4.0));
+    let m = g.move_stop(0, 0.7);
+    assert!(m.domain().0 < 0.5);
+    assert!(m.domain().0 > 0.499);
+
+    assert_eq!(g.move_stop(1, f32::NAN).segments()[0].2, 0.5);
+    assert_eq!(g.move_stop(0, f32::NEG_INFINITY).domain(), (0.0, 1.0));
+    assert_eq!(g.move_stop(2, f32::INFINITY).domain(), (0.0, 1.0));
+    assert_eq!(
+        g.move_stop(2, f32::INFINITY).at(0.5).to_css_hex(),
+        "#00ff00"
+    );
 }
@@ -3,34 +3,57 @@ use colorgrad::Gradient;
 #[test]
 fn preset() {
     let g = colorgrad::preset::viridis();
-    assert_eq!(g.at(0.0).to_hex_string(), "#440154");
-    assert_eq!(g.at(0.5).to_hex_string(), "#27838e");
-    assert_eq!(g.at(1.0).to_hex_string(), "#fee825");
-    assert_eq!(g.at(f32::NAN).to_hex_string(), "#000000");
+    assert_eq!(g.at(0.0).to_css_hex(), "#440154");
+    assert_eq!(g.at(0.5).to_css_hex(), "#27838e");
+    assert_eq!(g.at(1.0).to_css_hex(), "#fee825");
+    assert_eq!(g.at(f32::NAN).to_css_hex(), "#000000");
 
     let g = colorgrad::preset::greys();
-    assert_eq!(g.at(0.0).to_hex_string(), "#ffffff");
-    assert_eq!(g.at(1.0).to_hex_string(), "#000000");
+    assert_eq!(g.at(0.0).to_css_hex(), "#ffffff");
+    assert_eq!(g.at(1.0).to_css_hex(), "#000000");
 
     let g = colorgrad::preset::turbo();
-    assert_eq!(g.at(0.0).to_hex_string(), "#23171b");
-    assert_eq!(g.at(1.0).to_hex_string(), "#900c00");
+    assert_eq!(g.at(0.0).to_css_hex(), "#23171b");
+    assert_eq!(g.at(1.0).to_css_hex(), "#900c00");
 
     let g = colorgrad::preset::cividis();
-    assert_eq!(g.at(0.0).to_hex_string(), "#002051");
-    assert_eq!(g.at(1.0).to_hex_string(), "#fdea45");
+    assert_eq!(g.at(0.0).to_css_hex(), "#002051");
+    assert_eq!(g.at(1.0).to_css_hex(), "#fdea45");
 
     let g = colorgrad::preset::cubehelix_default();
-    assert_eq!(g.at(0.0).to_hex_string(), "#000000");
-    assert_eq!(g.at(1.0).to_hex_string(), "#ffffff");
+    assert_eq!(g.at(0.0).to_css_hex(), "#000000");
+    assert_eq!(g.at(1.0).to_css_hex(), "#ffffff");
+
+    // default parameters
+    let a = colorgrad::preset::cubehelix(0.5, -1.5, 1.0, 1.0);
+    let b = colorgrad::preset::cubehelix_default();
+    for i in 0..=100 {
+        let t = i as f32 / 100.0;
+        assert_eq!(a.at(t).to_rgba8(), b.at(t).to_rgba8());
+    }
+
+    // rotations change the hue path, not the lightness range
+    let c = colorgrad::preset::cubehelix(0.5, 1.0, 1.0, 1.0);
+    assert_eq!(c.at(0.0).to_css_hex(), "#000000");
+    assert_eq!(c.at(1.0).to_css_hex(), "#ffffff");
+    assert_ne!(c.at(0.25).to_rgba8(), a.at(0.25).to_rgba8());
+    assert_ne!(c.at(0.5).to_rgba8(), a.at(0.5).to_rgba8());
+
+    // gamma
+    let d = colorgrad::preset::cubehelix(0.5, -1.5, 1.0, 2.0);
+    assert!(d.at(0.5).to_oklaba()[0] < a.at(0.5).to_oklaba()[0]);
+
+    // no saturation
+    let gray = colorgrad::preset::cubehelix(0.5, -1.5, 0.0, 1.0);
+    assert_eq!(gray.at(0.5).to_css_hex(), "#808080");
 
     let g = colorgrad::preset::warm();
-    assert_eq!(g.at(0.0).to_hex_string(), "#6e40aa");
-    assert_eq!(g.at(1.0).to_hex_string(), "#aff05b");
+    assert_eq!(g.at(0.0).to_css_hex(), "#6e40aa");
+    assert_eq!(g.at(1.0).to_css_hex(), "#aff05b");
 
     let g = colorgrad::preset::cool();
-    assert_eq!(g.at(0.0).to_hex_string(), "#6e40aa");
-    assert_eq!(g.at(1.0).to_hex_string(), "#aff05b");
+    assert_eq!(g.at(0.0).to_css_hex(), "#6e40aa");
+    assert_eq!(g.at(1.0).to_css_hex(), "#aff05b");
 }
 
 #[test]
@@ -41,3 +64,170 @@ fn cyclic() {
     let g = colorgrad::preset::sinebow();
     assert_eq!(g.at(0.0).to_rgba8(), g.at(1.0).to_rgba8());
 }
+
+#[test]
+fn lch_profile() {
+    let g = colorgrad::preset::viridis();
+    let profile = g.lch_profile(100);
+    assert_eq!(profile.len(), 100);
+
+    for p in profile.windows(2) {
+        assert!(p[1][0] > p[0][0]);
+    }
+
+    for [l, c, h] in &profile {
+        assert!((0.0..=1.0).contains(l));
+        assert!(*c >= 0.0);
+        assert!((0.0..360.0).contains(h));
+    }
+
+    assert!(g.lch_profile(0).is_empty());
+}
+
+#[test]
+fn lut() {
+    let gradients: Vec<Box<dyn Gradient>> = vec![
+        Box::new(colorgrad::preset::turbo()),
+        Box::new(colorgrad::preset::cividis()),
+        Box::new(colorgrad::preset::sinebow()),
+        Box::new(colorgrad::preset::cubehelix_default()),
+    ];
+
+    for g in &gradients {
+        let lut = g.to_lut(256);
+        assert_eq!(lut.domain(), g.domain());
+
+        for i in 0..=1000 {
+            let t = i as f32 / 1000.0;
+            let a = g.at(t).to_array();
+            let b = lut.at(t).to_array();
+            for (a, b) in a.iter().zip(b) {
+                assert!((a - b).abs() < 2.0 / 255.0);
+            }
+        }
+    }
+
+    let g = colorgrad::preset::turbo();
+    let lut = colorgrad::preset::turbo_lut(1024);
+    for t in [0.0, 0.33, 0.5, 0.71, 1.0] {
+        let a = g.at(t).to_rgba8();
+        let b = lut.at(t).to_rgba8();
+        for (a, b) in a.iter().zip(b) {
+            assert!(a.abs_diff(b) <= 1);
+        }
+    }
+
+    assert_eq!(g.to_lut(0).at(1.0).to_rgba8(), g.at(1.0).to_rgba8());
+}
+
+#[test]
+fn to_builder() {
+    let g = colorgrad::preset::turbo();
+
+    let mut gb = g.to_builder(64);
+    assert_eq!(gb.get_colors().len(), 64);
+    assert_eq!(gb.get_positions().len(), 64);
+    assert_eq!(gb.get_positions()[0], 0.0);
+    assert_eq!(gb.get_positions()[63], 1.0);
+
+    let g2 = gb.build::<colorgrad::LinearGradient>().unwrap();
+    assert_eq!(g2.domain(), g.domain());
+
+    for i in 0..=1000 {
+        let t = i as f32 / 1000.0;
+        let a = g.at(t).to_array();
+        let b = g2.at(t).to_array();
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 0.02);
+        }
+    }
+
+    // still editable
+    let g3 = gb
+        .reverse()
+        .build::<colorgrad::CatmullRomGradient>()
+        .unwrap();
+    assert_eq!(g3.at(0.0).to_rgba8(), g.at(1.0).to_rgba8());
+    assert_eq!(g3.at(1.0).to_rgba8(), g.at(0.0).to_rgba8());
+
+    assert_eq!(g.to_builder(0).get_colors().len(), 2);
+}
+
+#[test]
+fn blend_presets() {
+    use colorgrad::preset::{blend_presets, magma, viridis};
+
+    let g = blend_presets("viridis", "magma", 0.5, 64).unwrap();
+    assert_eq!(g.domain(), (0.0, 1.0));
+
+    let dist = |a: colorgrad::Color, b: colorgrad::Color| {
+        let (a, b) = (a.to_oklaba(), b.to_oklaba());
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+    };
+
+    // the midpoint differs from both presets, and is between them
+    let (c, cv, cm) = (g.at(0.5), viridis().at(0.5), magma().at(0.5));
+    assert!(dist(c.clone(), cv.clone()) > 0.05);
+    assert!(dist(c.clone(), cm.clone()) > 0.05);
+    assert!((dist(c.clone(), cv.clone()) - dist(c, cm.clone())).abs() < 0.02);
+
+    // the ends of the weights range are the presets
+    let g = blend_presets("viridis", "magma", 0.0, 64).unwrap();
+    assert!(dist(g.at(0.5), cv) < 0.01);
+    let g = blend_presets("viridis", "magma", 1.0, 64).unwrap();
+    assert!(dist(g.at(0.5), cm) < 0.01);
+
+    assert!(blend_presets("turbo", "rd_bu", 0.3, 2).is_some());
+    assert!(blend_presets("viridis", "Magma", 0.5, 64).is_none());
+    assert!(blend_presets("", "magma", 0.5, 64).is_none());
+}
+
+#[test]
+fn at_signed() {
+    let g = colorgrad::preset::rd_bu();
+    assert_eq!(g.at_signed(-1.0), g.at(0.0));
+    assert_eq!(g.at_signed(0.0), g.at(0.5));
+    assert_eq!(g.at_signed(1.0), g.at(1.0));
+    assert_eq!(g.at_signed(-0.5), g.at(0.25));
+
+    // the neutral center color
+    let [l, c, _, _] = g.at_signed(0.0).to_oklcha();
+    assert!(l > 0.9);
+    assert!(c < 0.02);
+}
+
+#[test]
+fn mode() {
+    assert_eq!(colorgrad::preset::rainbow().mode(), None);
+    assert_eq!(colorgrad::preset::turbo().mode(), None);
+    assert_eq!(colorgrad::preset::cubehelix_default().mode(), None);
+    assert_eq!(
+        colorgrad::preset::viridis().mode(),
+        Some(colorgrad::BlendMode::Rgb)
+    );
+}
+
+#[test]
+fn stats() {
+    let s = colorgrad::preset::viridis().stats(256);
+    assert!(s.monotone_lightness);
+    assert!(!s.cyclic);
+    assert!(s.lightness_range.0 < 0.4 && s.lightness_range.1 > 0.9);
+    assert!(s.chroma_range.0 >= 0.0 && s.chroma_range.0 < s.chroma_range.1);
+    assert!(s.max_step.1 > 0.0 && s.max_step.1 < 0.02);
+
+    let s = colorgrad::preset::rainbow().stats(256);
+    assert!(!s.monotone_lightness);
+    assert!(s.cyclic);
+    assert!(s.lightness_range.0 < s.lightness_range.1);
+
+    // dense sampling, where each step is below the tolerance
+    let s = colorgrad::preset::rainbow().stats(2000);
+    assert!(!s.monotone_lightness);
+
+    let s = colorgrad::preset::greys().stats(100);
+    assert!(s.monotone_lightness);
+    assert_eq!(s.lightness_range.0, 0.0);
+    assert!((s.lightness_range.1 - 1.0).abs() < 1e-3);
+    assert!(s.chroma_range.1 < 1e-3);
+}
@@ -1,4 +1,4 @@
-use colorgrad::{Gradient, GradientBuilder, LinearGradient};
+use colorgrad::{BlendMode, Color, Gradient, GradientBuilder, LinearGradient};
 
 #[test]
 fn sharp_gradient() {
@@ -84,3 +84,155 @@ fn sharp_gradient_with_smoothness() {
     assert_eq!(g.at(1.5).to_rgba8(), [0, 0, 255, 255]);
     assert_eq!(g.at(f32::NAN).to_rgba8(), [0, 0, 0, 255]);
 }
+
+#[test]
+fn sharp_gradient_blend_mode() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    let g = GradientBuilder::new()
+        .colors(&[red.clone(), blue.clone()])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+
+    // sharp() uses the blend mode of the gradient
+    let g_oklab = g.sharp(2, 0.5);
+    let g_rgb = g.sharp_in(2, 0.5, BlendMode::Rgb);
+
+    assert_eq!(
+        g_oklab.at(0.5).to_rgba8(),
+        red.interpolate_oklab(&blue, 0.5).to_rgba8()
+    );
+    assert_eq!(g_rgb.at(0.5).to_rgba8(), [128, 0, 128, 255]);
+    assert_ne!(g_oklab.at(0.45).to_rgba8(), g_rgb.at(0.45).to_rgba8());
+
+    // flat parts are identical
+    for t in [0.0, 0.2, 0.8, 1.0] {
+        assert_eq!(g_oklab.at(t).to_rgba8(), g_rgb.at(t).to_rgba8());
+    }
+    assert_eq!(g_oklab.at(0.2).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(g_oklab.at(0.8).to_rgba8(), [0, 0, 255, 255]);
+}
+
+#[test]
+fn max_step() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let (_, smooth) = g.max_step(101);
+    assert!(smooth < 0.02);
+
+    let (pos, step) = g.sharp(2, 0.0).max_step(101);
+    assert!((pos - 0.5).abs() < 0.01);
+    assert!(step > 0.9);
+
+    let (pos, step) = g.sharp(4, 0.0).max_step(101);
+    assert!([0.25, 0.5, 0.75].iter().any(|p| (pos - p).abs() < 0.01));
+    assert!(step > 0.25);
+
+    assert_eq!(g.max_step(0), (0.0, 0.0));
+    assert_eq!(g.max_step(1), (0.0, 0.0));
+}
+
+#[test]
+fn min_feature_width() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#000", "#fff"])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let smooth = g.min_feature_width(1001);
+    assert!(smooth > 0.015 && smooth < 0.03);
+
+    let sharp = g.sharp(4, 0.0).min_feature_width(1001);
+    assert!(sharp <= 0.001 + 1e-6);
+    assert!(sharp < smooth / 10.0);
+
+    // no noticeable change
+    let g = GradientBuilder::new()
+        .html_colors(&["#777", "#777"])
+        .domain(&[10.0, 30.0])
+        .build::<LinearGradient>()
+        .unwrap();
+    assert_eq!(g.min_feature_width(100), 20.0);
+    assert_eq!(g.min_feature_width(100_000), 20.0);
+
+    // low contrast ramp, many samples
+    let g = GradientBuilder::new()
+        .html_colors(&["#808080", "#8a8a8a"])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+    let w = g.min_feature_width(100_000);
+    assert!(w > 0.3 && w < 1.0);
+
+    // non-monotone ramp, only the last two samples differ noticeably
+    let g = GradientBuilder::new()
+        .colors(&[
+            Color::from_oklaba(0.5, 0.0, 0.0, 1.0),
+            Color::from_oklaba(0.515, 0.0, 0.0, 1.0),
+            Color::from_oklaba(0.49, 0.0, 0.0, 1.0),
+        ])
+        .mode(BlendMode::Oklab)
+        .build::<LinearGradient>()
+        .unwrap();
+    assert!((g.min_feature_width(3) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn smoothstepped() {
+    let g = GradientBuilder::new()
+        .html_colors(&["#f00", "#0f0", "#00f"])
+        .domain(&[-1.0, 1.0])
+        .build::<LinearGradient>()
+        .unwrap();
+
+    let s = g.smoothstepped(3);
+    assert_eq!(s.domain(), (-1.0, 1.0));
+    assert_eq!(s.at(-1.0).to_css_hex(), "#ff0000");
+    assert_eq!(s.at(0.0).to_css_hex(), "#00ff00");
+    assert_eq!(s.at(1.0).to_css_hex(), "#0000ff");
+    assert_eq!(s.at(-2.0).to_css_hex(), "#ff0000");
+    assert_eq!(s.at(2.0).to_css_hex(), "#0000ff");
+    assert_eq!(s.at(f32::NAN).to_css_hex(), "#000000");
+
+    // smoothstep: 3t^2 - 2t^3
+    for t in [0.1_f32, 0.25, 0.5, 0.8] {
+        let e = (3.0 - 2.0 * t) * t * t;
+        let c = s.at(-1.0 + t);
+        assert!((c.r - (1.0 - e)).abs() < 1e-5);
+        assert!((c.g - e).abs() < 1e-5);
+        assert_eq!(c.b, 0.0);
+    }
+
+    // eases in and out, flatter than linear near the colors
+    assert!(s.at(-0.9).r > g.at(-0.9).r);
+    assert!(s.at(-0.1).g > g.at(-0.1).g);
+
+    let segments = s
+        .segments()
+        .iter()
+        .map(|(p0, c0, p1, c1)| (*p0, c0.to_css_hex(), *p1, c1.to_css_hex()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        segments,
+        vec![
+            (-1.0, "#ff0000".to_string(), 0.0, "#00ff00".to_string()),
+            (0.0, "#00ff00".to_string(), 1.0, "#0000ff".to_string()),
+        ]
+    );
+
+    assert_eq!(s.snap_to_stop(-0.6).to_css_hex(), "#ff0000");
+    assert_eq!(s.snap_to_stop(-0.4).to_css_hex(), "#00ff00");
+    assert_eq!(s.snap_to_stop(0.7).to_css_hex(), "#0000ff");
+    assert_eq!(s.snap_to_stop(5.0).to_css_hex(), "#0000ff");
+
+    let s = g.smoothstepped(0);
+    assert_eq!(s.at(-1.0).to_css_hex(), "#ff0000");
+    assert_eq!(s.at(1.0).to_css_hex(), "#0000ff");
+}
@@ -3,7 +3,7 @@ use colorgrad::Color;
 pub fn colors2hex(colors: &[Color]) -> Vec<String> {
     let mut res = Vec::with_capacity(colors.len());
     for c in colors {
-        res.push(c.to_hex_string());
+        res.push(c.to_css_hex());
     }
     res
 }